@@ -37,7 +37,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Configure NATR with 14-period and Wilder smoothing
-    let config = Config::new_f64_wilder(14, 50);
+    let config = Config::wilder(14, 50);
 
     // Calculate NATR
     let natr_values = natr_series(&candles, &config)?;
@@ -49,9 +49,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Display the last 5 NATR values
-    let start_idx = natr_values.len().saturating_sub(5);
-    for i in start_idx..natr_values.len() {
-        println!("NATR[{}]: {:.4}%", i, natr_values[i]);
+    let start_idx = natr_values.end().saturating_sub(5).max(natr_values.start());
+    for i in start_idx..natr_values.end() {
+        match natr_values.get_bar(i) {
+            Some(natr) => println!("NATR[{}]: {:.4}%", i, natr),
+            None => println!("NATR[{}]: warming up", i),
+        }
     }
 
     Ok(())
@@ -19,7 +19,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Configure RSI with 14-period EMA smoothing
-    let config = Config::new_f64(14, 50);
+    let config = Config::ema(14, 50);
 
     // Calculate RSI
     let rsi_values = rsi_series(&candles, &config)?;
@@ -31,17 +31,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Display the last 10 RSI values with prices
-    let start_idx = rsi_values.len().saturating_sub(10);
-    for i in start_idx..rsi_values.len() {
-        let price_idx = i + (candles.len() - rsi_values.len());
-        println!(
-            "Price: {:.2} | RSI[{}]: {:.2}",
-            prices[price_idx], i, rsi_values[i]
-        );
+    let start_idx = rsi_values.end().saturating_sub(10).max(rsi_values.start());
+    for (i, price) in prices.iter().enumerate().skip(start_idx) {
+        match rsi_values.get_bar(i) {
+            Some(rsi) => println!("Price: {:.2} | RSI[{}]: {:.2}", price, i, rsi),
+            None => println!("Price: {:.2} | RSI[{}]: warming up", price, i),
+        }
     }
 
     println!();
-    if let Some(&last_rsi) = rsi_values.last() {
+    if let Some(last_rsi) = rsi_values.last() {
         let interpretation = match last_rsi {
             rsi if rsi > 70.0 => "Overbought (>70)",
             rsi if rsi < 30.0 => "Oversold (<30)",
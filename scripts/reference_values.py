@@ -0,0 +1,225 @@
+"""Expected values for the moving average and MACD unit tests.
+
+Plain Python implementations of each average, written from the published
+definitions independently of the Rust code, and evaluated on the `CLOSES`
+fixture in `src/test_utils.rs`. Run from the repository root:
+
+    python3 scripts/reference_values.py
+
+Each average prints `(first valid index, first three valid values, last value)`,
+and MACD prints `(MACD, signal, histogram)` from the first candle with a histogram.
+
+When `talib` or `pandas_ta` is importable, the script also prints the library
+output for the averages that follow the library, so the two can be compared.
+ALMA and ZLEMA deliberately differ from pandas-ta and are not compared:
+
+* ALMA weights the window from the oldest to the newest input, as in Legoux's
+  definition, so an offset of 0.85 favours recent inputs. `pandas_ta.alma`
+  applies the weights from the newest input backwards and starts one input later.
+* ZLEMA seeds its EMA with the mean of the first `period` de-lagged inputs.
+  `pandas_ta.zlma` averages whichever of the first `period` inputs are defined,
+  so its first value is at index `period - 1`.
+"""
+
+import math
+import re
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+
+
+def load_closes():
+    source = (ROOT / "src" / "test_utils.rs").read_text()
+    body = re.search(r"CLOSES: \[f64; \d+\] = \[(.*?)\];", source, re.S).group(1)
+    return [float(value) for value in re.findall(r"-?\d+\.\d+", body)]
+
+
+def valid(values):
+    """Returns the index of the first value and the values from there on."""
+    first = next(i for i, value in enumerate(values) if value is not None)
+    return first, values[first:]
+
+
+def pad(first, values):
+    return [None] * first + values
+
+
+def sma(values, period):
+    return [
+        None if i + 1 < period else sum(values[i + 1 - period : i + 1]) / period
+        for i in range(len(values))
+    ]
+
+
+def wma(values, period):
+    total = period * (period + 1) / 2
+    return [
+        None
+        if i + 1 < period
+        else sum((j + 1) * values[i + 1 - period + j] for j in range(period)) / total
+        for i in range(len(values))
+    ]
+
+
+def sma_seeded(values, period, alpha):
+    """Exponential mean of the defined values, seeded with their first simple mean."""
+    first, values = valid(values)
+    out = [None] * (period - 1)
+    mean = sum(values[:period]) / period
+    out.append(mean)
+    for value in values[period:]:
+        mean += alpha * (value - mean)
+        out.append(mean)
+    return pad(first, out)
+
+
+def ema(values, period):
+    return sma_seeded(values, period, 2 / (period + 1))
+
+
+def wilder(values, period):
+    return sma_seeded(values, period, 1 / period)
+
+
+def combine(weights, *lines):
+    return [
+        None if any(v is None for v in row) else sum(w * v for w, v in zip(weights, row))
+        for row in zip(*lines)
+    ]
+
+
+def dema(values, period):
+    e1 = ema(values, period)
+    e2 = ema(e1, period)
+    return combine([2, -1], e1, e2)
+
+
+def tema(values, period):
+    e1 = ema(values, period)
+    e2 = ema(e1, period)
+    e3 = ema(e2, period)
+    return combine([3, -3, 1], e1, e2, e3)
+
+
+def hma(values, period):
+    diff = combine([2, -1], wma(values, max(period // 2, 1)), wma(values, period))
+    first, diff = valid(diff)
+    return pad(first, wma(diff, max(math.isqrt(period), 1)))
+
+
+def zlema(values, period):
+    lag = (period - 1) // 2
+    delagged = [None] * lag + [2 * values[i] - values[i - lag] for i in range(lag, len(values))]
+    return ema(delagged, period)
+
+
+def kama(values, period, fast=2, slow=30):
+    fast = 2 / (fast + 1)
+    slow = 2 / (slow + 1)
+    out = [None] * period
+    prev = values[period - 1]
+    for i in range(period, len(values)):
+        signal = abs(values[i] - values[i - period])
+        noise = sum(abs(values[j] - values[j - 1]) for j in range(i - period + 1, i + 1))
+        efficiency = signal / noise if noise > 0 else 1.0
+        constant = (efficiency * (fast - slow) + slow) ** 2
+        prev += constant * (values[i] - prev)
+        out.append(prev)
+    return out
+
+
+def alma(values, period, offset=0.85, sigma=6.0):
+    m = offset * (period - 1)
+    s = period / sigma
+    weights = [math.exp(-((i - m) ** 2) / (2 * s * s)) for i in range(period)]
+    total = sum(weights)
+    return [
+        None
+        if i + 1 < period
+        else sum(w * values[i + 1 - period + j] for j, w in enumerate(weights)) / total
+        for i in range(len(values))
+    ]
+
+
+def t3(values, period, v_factor=0.7):
+    a = v_factor
+    emas = [values]
+    for _ in range(6):
+        emas.append(ema(emas[-1], period))
+    weights = [-(a**3), 3 * a**2 + 3 * a**3, -6 * a**2 - 3 * a - 3 * a**3, 1 + 3 * a + a**3 + 3 * a**2]
+    return combine(weights, emas[6], emas[5], emas[4], emas[3])
+
+
+def macd(values, fast=12, slow=26, signal=9):
+    line = combine([1, -1], ema(values, fast), ema(values, slow))
+    signal_line = ema(line, signal)
+    return line, signal_line, combine([1, -1], line, signal_line)
+
+
+# (label, reference, TA-Lib call, pandas-ta call); the calls take `close`
+AVERAGES = [
+    ("Sma(5)", lambda c: sma(c, 5), "SMA(close, 5)", "sma(close, 5)"),
+    ("Ema(5)", lambda c: ema(c, 5), "EMA(close, 5)", "ema(close, 5)"),
+    ("Wilder(5)", lambda c: wilder(c, 5), None, None),
+    ("Wma(5)", lambda c: wma(c, 5), "WMA(close, 5)", "wma(close, 5)"),
+    ("Dema(5)", lambda c: dema(c, 5), "DEMA(close, 5)", None),
+    ("Tema(4)", lambda c: tema(c, 4), "TEMA(close, 4)", None),
+    ("Hma(9)", lambda c: hma(c, 9), None, "hma(close, 9)"),
+    ("Zlema(6)", lambda c: zlema(c, 6), None, None),
+    ("kama(10)", lambda c: kama(c, 10), "KAMA(close, 10)", None),
+    ("alma(9)", lambda c: alma(c, 9), None, None),
+    ("t3(4)", lambda c: t3(c, 4), "T3(close, 4, vfactor=0.7)", None),
+]
+
+
+def summary(values):
+    values = [None if v is None or v != v else float(v) for v in values]
+    first, rest = valid(values)
+    return first, rest[:3], rest[-1]
+
+
+def main():
+    closes = load_closes()
+
+    try:
+        import numpy
+        import talib
+    except ImportError:
+        talib = None
+    try:
+        import pandas
+        import pandas_ta
+    except ImportError:
+        pandas_ta = None
+
+    for label, reference, talib_call, pandas_ta_call in AVERAGES:
+        print(f"{label}: {summary(reference(closes))}")
+        if talib is not None and talib_call:
+            namespace = {"close": numpy.array(closes)}
+            print(f"  talib.{talib_call}: {summary(eval(f'talib.{talib_call}', {'talib': talib}, namespace))}")
+        if pandas_ta is not None and pandas_ta_call:
+            namespace = {"close": pandas.Series(closes)}
+            result = eval(f"pandas_ta.{pandas_ta_call}", {"pandas_ta": pandas_ta}, namespace)
+            print(f"  pandas_ta.{pandas_ta_call}: {summary(result.tolist())}")
+
+    # (MACD, signal, histogram) from the first candle with a histogram
+    line, signal_line, histogram = macd(closes)
+    first, _ = valid(histogram)
+    print(f"macd(12, 26, 9): first MACD {valid(line)[0]}, first histogram {first}")
+    for i in range(first, first + 6):
+        print(f"  {(line[i], signal_line[i], histogram[i])}")
+    if talib is not None:
+        lines = talib.MACD(numpy.array(closes), 12, 26, 9)
+        print("  talib.MACD(close, 12, 26, 9):")
+        for i in range(first, first + 6):
+            print(f"    {tuple(float(l[i]) for l in lines)}")
+    if pandas_ta is not None:
+        # Columns are MACD, histogram, signal; `talib=False` keeps pandas-ta's own EMA
+        frame = pandas_ta.macd(pandas.Series(closes), 12, 26, 9, talib=False)
+        print("  pandas_ta.macd(close, 12, 26, 9, talib=False):")
+        for line_value, histogram_value, signal_value in frame.iloc[first : first + 6].itertuples(index=False):
+            print(f"    {(line_value, signal_value, histogram_value)}")
+
+
+if __name__ == "__main__":
+    main()
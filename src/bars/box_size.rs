@@ -0,0 +1,78 @@
+//! Box sizes for price-driven bars.
+
+use crate::{
+    core::{CandleSeriesView, Error, Numeric},
+    indicators::{Config, skipped_changes, smooth},
+};
+
+/// Price distance covered by a Renko brick or range bar.
+#[derive(Debug, Clone)]
+pub enum BoxSize<T> {
+    /// A fixed price distance.
+    Fixed(T),
+    /// The latest average true range of the source candles, smoothed with the
+    /// configuration's smoothing over the last `max_history` candles.
+    Atr(Config<T>),
+}
+
+impl<T: Numeric> BoxSize<T> {
+    /// Resolves the box size against a candle series.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if a fixed size is not a positive, finite
+    /// number or the ATR configuration is invalid, or
+    /// `Error::NotEnoughData` if the series is too short, or too flat, to derive
+    /// a positive ATR.
+    pub fn resolve<'a>(&self, candles: impl Into<CandleSeriesView<'a, T>>) -> Result<T, Error>
+    where
+        T: 'a,
+    {
+        match self {
+            BoxSize::Fixed(size) => {
+                if !is_valid(*size) {
+                    return Err(Error::InvalidConfig(format!(
+                        "box size must be positive and finite, got {size:?}"
+                    )));
+                }
+                Ok(*size)
+            }
+            BoxSize::Atr(config) => {
+                config.validate()?;
+                let candles = candles.into();
+                if candles.len() < config.warmup() + 1 {
+                    return Err(Error::NotEnoughData);
+                }
+
+                let tr = candles.true_range(Some(config.max_history));
+                let atr = smooth(tr, config, skipped_changes(candles.len(), config));
+                match atr.last() {
+                    Some(&atr) if atr.is_positive() => Ok(atr),
+                    _ => Err(Error::NotEnoughData),
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether `size` can be used as a box size.
+pub(crate) fn is_valid<T: Numeric>(size: T) -> bool {
+    size.is_positive() && size.is_finite()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CandleSeries;
+
+    #[test]
+    fn test_invalid_fixed_size() {
+        let candles = CandleSeries::<f64>::new(60);
+        for size in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                BoxSize::Fixed(size).resolve(&candles),
+                Err(Error::InvalidConfig(_))
+            ));
+        }
+        assert_eq!(BoxSize::Fixed(0.5).resolve(&candles).unwrap(), 0.5);
+    }
+}
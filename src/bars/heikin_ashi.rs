@@ -0,0 +1,122 @@
+//! Heikin-Ashi candles.
+
+use crate::core::{Candle, CandleSeries, CandleSeriesView, Numeric};
+
+/// Transforms a candle series into Heikin-Ashi candles.
+///
+/// # Algorithm
+///
+/// ```text
+/// HA_close[i] = (open[i] + high[i] + low[i] + close[i]) / 4
+/// HA_open[i]  = (HA_open[i-1] + HA_close[i-1]) / 2, or (open[0] + close[0]) / 2
+/// HA_high[i]  = max(high[i], HA_open[i], HA_close[i])
+/// HA_low[i]   = min(low[i], HA_open[i], HA_close[i])
+/// ```
+///
+/// Volumes, timestamps, the timeframe and the maximum length of the source
+/// series are carried over unchanged.
+pub fn heikin_ashi<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
+) -> CandleSeries<T> {
+    let candles = candles.into();
+    let mut transformed = match candles.max_len() {
+        Some(max_len) => CandleSeries::with_max_len(candles.timeframe(), max_len),
+        None => CandleSeries::new(candles.timeframe()),
+    };
+
+    let two = T::two();
+    let four = two * two;
+    let mut prev: Option<Candle<T>> = None;
+
+    for i in 0..candles.len() {
+        let candle = candles.get_owned(i).unwrap();
+        let close = (candle.open + candle.high + candle.low + candle.close) / four;
+        let open = match prev {
+            Some(prev) => (prev.open + prev.close) / two,
+            None => (candle.open + candle.close) / two,
+        };
+
+        let high = candle.high.max(open).max(close);
+        let low = candle.low.min(open).min(close);
+
+        let ha = Candle {
+            open,
+            high,
+            low,
+            close,
+            volume: candle.volume,
+        };
+        transformed.push_candle_unchecked(ha, candles.timestamps()[i]);
+        prev = Some(ha);
+    }
+
+    transformed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heikin_ashi() {
+        let mut candles = CandleSeries::new(60);
+        let data = [
+            (10.0, 12.0, 9.0, 11.0),
+            (11.0, 13.0, 10.0, 12.0),
+            (12.0, 12.5, 8.0, 9.0),
+        ];
+        for (i, &(open, high, low, close)) in data.iter().enumerate() {
+            let candle = Candle {
+                open,
+                high,
+                low,
+                close,
+                volume: 1.0,
+            };
+            candles.push_candle_unchecked(candle, i as u64 * 60);
+        }
+
+        let ha = heikin_ashi(&candles);
+        assert_eq!(ha.len(), 3);
+        assert_eq!(ha.timeframe(), candles.timeframe());
+        assert_eq!(
+            ha.get_owned(0).unwrap(),
+            Candle {
+                open: 10.5,
+                high: 12.0,
+                low: 9.0,
+                close: 10.5,
+                volume: 1.0,
+            }
+        );
+        assert_eq!(
+            ha.get_owned(1).unwrap(),
+            Candle {
+                open: 10.5,
+                high: 13.0,
+                low: 10.0,
+                close: 11.5,
+                volume: 1.0,
+            }
+        );
+        let last = ha.get_owned(2).unwrap();
+        assert_eq!(last.open, 11.0);
+        assert_eq!(last.close, 10.375);
+        assert_eq!(last.high, 12.5);
+        assert_eq!(last.low, 8.0);
+        assert_eq!(ha.max_len(), None);
+    }
+
+    #[test]
+    fn test_heikin_ashi_keeps_max_len() {
+        let mut candles = CandleSeries::with_max_len(60, 2);
+        for i in 0..4 {
+            candles.push(10.0 + i as f64, 1.0, i * 60).unwrap();
+        }
+
+        let ha = heikin_ashi(&candles);
+        assert_eq!(ha.max_len(), Some(2));
+        assert_eq!(ha.len(), 2);
+        assert_eq!(ha.timestamps().as_slice(), candles.timestamps().as_slice());
+    }
+}
@@ -0,0 +1,18 @@
+//! Alternative bar construction: tick, volume and dollar bars, Heikin-Ashi
+//! candles, Renko bricks and range bars.
+
+mod box_size;
+mod heikin_ashi;
+mod range;
+mod renko;
+mod threshold;
+
+pub use box_size::*;
+pub use heikin_ashi::*;
+pub use range::*;
+pub use renko::*;
+pub use threshold::*;
+
+/// Default maximum number of bars or bricks completed by a single push to
+/// [`BarBuilder`], [`RenkoBuilder`] or [`RangeBarBuilder`].
+pub const DEFAULT_MAX_BARS_PER_PUSH: usize = 10_000;
@@ -0,0 +1,297 @@
+//! Range bars.
+
+use crate::core::{Candle, CandleSeries, CandleSeriesView, Error, Numeric, Timeframe};
+
+use super::{BoxSize, DEFAULT_MAX_BARS_PER_PUSH};
+
+/// Builds range bars from a stream of prices.
+///
+/// Each bar spans at most one box from its high to its low. When a price would
+/// stretch the bar further, the bar is closed at its low plus one box (or high
+/// minus one box) and the next bar opens at that level; large jumps produce as
+/// many intermediate bars as needed. Bars are timestamped with their first price
+/// and trade volume is added to the bar containing the traded price.
+#[derive(Debug, Clone)]
+pub struct RangeBarBuilder<T> {
+    box_size: T,
+    series: CandleSeries<T>,
+    current: Option<(Candle<T>, u64)>,
+    max_bars_per_push: usize,
+}
+
+impl<T: Numeric> RangeBarBuilder<T> {
+    /// Creates a new range bar builder.
+    ///
+    /// # Panics
+    /// Panics if `box_size` is not a positive, finite number. Use
+    /// [`BoxSize::resolve`] to validate untrusted sizes.
+    pub fn new(box_size: T) -> Self {
+        if !super::box_size::is_valid(box_size) {
+            panic!("box size must be positive and finite");
+        }
+
+        Self {
+            box_size,
+            series: CandleSeries::new(Timeframe::Irregular),
+            current: None,
+            max_bars_per_push: DEFAULT_MAX_BARS_PER_PUSH,
+        }
+    }
+
+    /// Returns the box size.
+    pub fn box_size(&self) -> T {
+        self.box_size
+    }
+
+    /// Returns the completed bars.
+    pub fn series(&self) -> &CandleSeries<T> {
+        &self.series
+    }
+
+    /// Consumes the builder and returns the completed bars.
+    pub fn into_series(self) -> CandleSeries<T> {
+        self.series
+    }
+
+    /// Returns the in-progress bar and its start timestamp, if any.
+    pub fn partial(&self) -> Option<(Candle<T>, u64)> {
+        self.current
+    }
+
+    /// Returns the maximum number of bars a single price may complete.
+    pub fn max_bars_per_push(&self) -> usize {
+        self.max_bars_per_push
+    }
+
+    /// Sets the maximum number of bars a single price may complete.
+    ///
+    /// A price that would complete more bars, such as a bad tick far away from
+    /// the current bar, is rejected by [`RangeBarBuilder::push`]. Defaults to
+    /// [`DEFAULT_MAX_BARS_PER_PUSH`](super::DEFAULT_MAX_BARS_PER_PUSH).
+    pub fn set_max_bars_per_push(&mut self, max_bars_per_push: usize) {
+        self.max_bars_per_push = max_bars_per_push;
+    }
+
+    /// Pushes a price and returns the number of bars it completed.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidInput` if the price or volume is not finite, or the
+    /// price would complete more than [`RangeBarBuilder::max_bars_per_push`] bars.
+    /// The builder is left unchanged on error.
+    pub fn push(&mut self, price: T, vol: T, ts: u64) -> Result<usize, Error> {
+        if !price.is_finite() || !vol.is_finite() {
+            return Err(Error::InvalidInput(format!(
+                "price ({price:?}) and volume ({vol:?}) must be finite"
+            )));
+        }
+        let Some((mut candle, mut start)) = self.current else {
+            self.current = Some((flat(price, vol), ts));
+            return Ok(0);
+        };
+
+        let mut completed = Vec::new();
+        loop {
+            let ceiling = candle.low + self.box_size;
+            let floor = candle.high - self.box_size;
+
+            let level = if price > ceiling {
+                candle.high = ceiling;
+                ceiling
+            } else if price < floor {
+                candle.low = floor;
+                floor
+            } else {
+                break;
+            };
+
+            candle.close = level;
+            completed.push((candle, start));
+            if completed.len() > self.max_bars_per_push {
+                return Err(Error::InvalidInput(format!(
+                    "price {price:?} would complete more than {} bars",
+                    self.max_bars_per_push
+                )));
+            }
+
+            candle = flat(level, T::ZERO);
+            start = ts;
+        }
+
+        if price > candle.high {
+            candle.high = price;
+        }
+        if price < candle.low {
+            candle.low = price;
+        }
+        candle.close = price;
+        candle.volume += vol;
+
+        for &(bar, start) in &completed {
+            self.series.push_candle_unchecked(bar, start);
+        }
+        self.current = Some((candle, start));
+        Ok(completed.len())
+    }
+}
+
+/// Builds range bars from a candle series.
+///
+/// Each candle is replayed as the price path open, then the nearer of high and
+/// low, then the other extreme, then close, with its volume traded at the close.
+/// The in-progress bar is included as the last bar.
+///
+/// # Errors
+/// Returns `Error::InvalidConfig` or `Error::NotEnoughData` if an ATR-derived box
+/// size cannot be resolved, or `Error::InvalidInput` if a price is not finite or
+/// moves too many boxes at once; see [`RangeBarBuilder::push`].
+pub fn range_bars<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
+    box_size: &BoxSize<T>,
+) -> Result<CandleSeries<T>, Error> {
+    let candles = candles.into();
+    let mut builder = RangeBarBuilder::new(box_size.resolve(candles)?);
+
+    for i in 0..candles.len() {
+        let candle = candles.get_owned(i).unwrap();
+        let ts = candles.timestamps()[i];
+
+        let (first, second) =
+            if (candle.high - candle.open).abs() < (candle.open - candle.low).abs() {
+                (candle.high, candle.low)
+            } else {
+                (candle.low, candle.high)
+            };
+        for price in [candle.open, first, second] {
+            builder.push(price, T::ZERO, ts)?;
+        }
+        builder.push(candle.close, candle.volume, ts)?;
+    }
+
+    let mut series = builder.series;
+    if let Some((candle, ts)) = builder.current {
+        series.push_candle_unchecked(candle, ts);
+    }
+    Ok(series)
+}
+
+fn flat<T: Numeric>(price: T, vol: T) -> Candle<T> {
+    Candle {
+        open: price,
+        high: price,
+        low: price,
+        close: price,
+        volume: vol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_bar_builder() {
+        let mut builder = RangeBarBuilder::new(1.0);
+        assert_eq!(builder.push(10.0, 1.0, 0).unwrap(), 0);
+        assert_eq!(builder.push(10.6, 1.0, 1).unwrap(), 0);
+        // Gap up through two full boxes
+        assert_eq!(builder.push(12.5, 2.0, 2).unwrap(), 2);
+
+        let series = builder.series();
+        assert_eq!(
+            series.get_owned(0).unwrap(),
+            Candle {
+                open: 10.0,
+                high: 11.0,
+                low: 10.0,
+                close: 11.0,
+                volume: 2.0,
+            }
+        );
+        assert_eq!(
+            series.get_owned(1).unwrap(),
+            Candle {
+                open: 11.0,
+                high: 12.0,
+                low: 11.0,
+                close: 12.0,
+                volume: 0.0,
+            }
+        );
+        assert_eq!(series.timestamps().as_slice(), &[0, 2]);
+
+        let (partial, ts) = builder.partial().unwrap();
+        assert_eq!(
+            (partial.open, partial.close, partial.volume, ts),
+            (12.0, 12.5, 2.0, 2)
+        );
+
+        // A drop closes the bar at its high minus one box
+        assert_eq!(builder.push(11.2, 1.0, 3).unwrap(), 1);
+        assert_eq!(builder.series().get_owned(2).unwrap().close, 11.5);
+    }
+
+    #[test]
+    fn test_range_bars_from_candles() {
+        let mut candles = CandleSeries::new(60);
+        let candle = Candle {
+            open: 10.0,
+            high: 10.4,
+            low: 9.0,
+            close: 9.5,
+            volume: 5.0,
+        };
+        candles.push_candle_unchecked(candle, 0);
+
+        // Path 10.0 -> 10.4 -> 9.0 -> 9.5 with a box of 1.0
+        let series = range_bars(&candles, &BoxSize::Fixed(1.0)).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.get_owned(0).unwrap().close, 9.4);
+        assert_eq!(series.get_owned(1).unwrap().volume, 5.0);
+        assert!(
+            range_bars(
+                &candles,
+                &BoxSize::Atr(crate::indicators::Config::ema(14, 20))
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_range_bars_reject_invalid_prices() {
+        let mut builder = RangeBarBuilder::new(1.0);
+        assert!(builder.push(f64::NAN, 1.0, 0).is_err());
+        assert_eq!(builder.push(10.0, 1.0, 0).unwrap(), 0);
+        for price in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            assert!(matches!(
+                builder.push(price, 1.0, 1),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+
+        // A jump past the bar limit leaves the builder unchanged
+        builder.set_max_bars_per_push(3);
+        assert!(matches!(
+            builder.push(14.5, 1.0, 1),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(builder.push(-1e12, 1.0, 1).is_err());
+        assert!(builder.series().is_empty());
+        assert_eq!(builder.partial().unwrap().0.close, 10.0);
+        assert_eq!(builder.push(13.5, 1.0, 1).unwrap(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "box size must be positive and finite")]
+    fn test_range_bar_invalid_box_size() {
+        RangeBarBuilder::new(f64::NAN);
+    }
+
+    #[test]
+    fn test_range_bars_reject_invalid_box_size() {
+        let candles = CandleSeries::<f64>::new(60);
+        assert!(matches!(
+            range_bars(&candles, &BoxSize::Fixed(0.0)),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,265 @@
+//! Renko bricks.
+
+use crate::core::{Candle, CandleSeries, CandleSeriesView, Error, Numeric, Timeframe};
+
+use super::{BoxSize, DEFAULT_MAX_BARS_PER_PUSH};
+
+/// Builds Renko bricks from a stream of prices.
+///
+/// A new brick is added each time the price moves one box beyond the top or
+/// bottom of the last brick, so continuing a trend takes one box and reversing
+/// it takes two. Bricks have no wicks, are timestamped with the price that
+/// completed them, and carry the volume traded since the previous brick on the
+/// first brick of each move.
+#[derive(Debug, Clone)]
+pub struct RenkoBuilder<T> {
+    box_size: T,
+    series: CandleSeries<T>,
+    /// Top and bottom of the last brick, or the first price before any brick.
+    range: Option<(T, T)>,
+    pending_volume: T,
+    max_bars_per_push: usize,
+}
+
+impl<T: Numeric> RenkoBuilder<T> {
+    /// Creates a new Renko builder.
+    ///
+    /// # Panics
+    /// Panics if `box_size` is not a positive, finite number. Use
+    /// [`BoxSize::resolve`] to validate untrusted sizes.
+    pub fn new(box_size: T) -> Self {
+        if !super::box_size::is_valid(box_size) {
+            panic!("box size must be positive and finite");
+        }
+
+        Self {
+            box_size,
+            series: CandleSeries::new(Timeframe::Irregular),
+            range: None,
+            pending_volume: T::ZERO,
+            max_bars_per_push: DEFAULT_MAX_BARS_PER_PUSH,
+        }
+    }
+
+    /// Returns the box size.
+    pub fn box_size(&self) -> T {
+        self.box_size
+    }
+
+    /// Returns the completed bricks.
+    pub fn series(&self) -> &CandleSeries<T> {
+        &self.series
+    }
+
+    /// Consumes the builder and returns the completed bricks.
+    pub fn into_series(self) -> CandleSeries<T> {
+        self.series
+    }
+
+    /// Returns the maximum number of bricks a single price may complete.
+    pub fn max_bars_per_push(&self) -> usize {
+        self.max_bars_per_push
+    }
+
+    /// Sets the maximum number of bricks a single price may complete.
+    ///
+    /// A price that would complete more bricks, such as a bad tick far away from
+    /// the last brick, is rejected by [`RenkoBuilder::push`]. Defaults to
+    /// [`DEFAULT_MAX_BARS_PER_PUSH`](super::DEFAULT_MAX_BARS_PER_PUSH).
+    pub fn set_max_bars_per_push(&mut self, max_bars_per_push: usize) {
+        self.max_bars_per_push = max_bars_per_push;
+    }
+
+    /// Pushes a price and returns the number of bricks it completed.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidInput` if the price or volume is not finite, or the
+    /// price would complete more than [`RenkoBuilder::max_bars_per_push`] bricks.
+    /// The builder is left unchanged on error.
+    pub fn push(&mut self, price: T, vol: T, ts: u64) -> Result<usize, Error> {
+        if !price.is_finite() || !vol.is_finite() {
+            return Err(Error::InvalidInput(format!(
+                "price ({price:?}) and volume ({vol:?}) must be finite"
+            )));
+        }
+        let Some((mut top, mut bottom)) = self.range else {
+            self.pending_volume += vol;
+            self.range = Some((price, price));
+            return Ok(0);
+        };
+
+        let mut bricks = Vec::new();
+        while price >= top + self.box_size {
+            bricks.push((top, top + self.box_size));
+            bottom = top;
+            top += self.box_size;
+            self.check_bricks(bricks.len(), price)?;
+        }
+        while price <= bottom - self.box_size {
+            bricks.push((bottom, bottom - self.box_size));
+            top = bottom;
+            bottom -= self.box_size;
+            self.check_bricks(bricks.len(), price)?;
+        }
+
+        self.pending_volume += vol;
+        for &(open, close) in &bricks {
+            self.push_brick(open, close, ts);
+        }
+        self.range = Some((top, bottom));
+        Ok(bricks.len())
+    }
+
+    fn check_bricks(&self, bricks: usize, price: T) -> Result<(), Error> {
+        if bricks > self.max_bars_per_push {
+            return Err(Error::InvalidInput(format!(
+                "price {price:?} would complete more than {} bricks",
+                self.max_bars_per_push
+            )));
+        }
+        Ok(())
+    }
+
+    fn push_brick(&mut self, open: T, close: T, ts: u64) {
+        let (high, low) = if close > open {
+            (close, open)
+        } else {
+            (open, close)
+        };
+        let brick = Candle {
+            open,
+            high,
+            low,
+            close,
+            volume: self.pending_volume,
+        };
+        self.series.push_candle_unchecked(brick, ts);
+        self.pending_volume = T::ZERO;
+    }
+}
+
+/// Builds Renko bricks from the closing prices of a candle series.
+///
+/// # Errors
+/// Returns `Error::InvalidConfig` or `Error::NotEnoughData` if an ATR-derived box
+/// size cannot be resolved, or `Error::InvalidInput` if a close is not finite or
+/// moves too many boxes at once; see [`RenkoBuilder::push`].
+pub fn renko<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
+    box_size: &BoxSize<T>,
+) -> Result<CandleSeries<T>, Error> {
+    let candles = candles.into();
+    let mut builder = RenkoBuilder::new(box_size.resolve(candles)?);
+
+    for i in 0..candles.len() {
+        builder.push(
+            candles.closes()[i],
+            candles.volumes()[i],
+            candles.timestamps()[i],
+        )?;
+    }
+
+    Ok(builder.into_series())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bricks(series: &CandleSeries<f64>) -> Vec<(f64, f64)> {
+        (0..series.len())
+            .map(|i| {
+                let brick = series.get_owned(i).unwrap();
+                (brick.open, brick.close)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_renko_builder() {
+        let mut builder = RenkoBuilder::new(1.0);
+        assert_eq!(builder.push(10.0, 1.0, 0).unwrap(), 0);
+        assert_eq!(builder.push(10.5, 1.0, 1).unwrap(), 0);
+        assert_eq!(builder.push(12.2, 1.0, 2).unwrap(), 2);
+        // A one box pullback does not reverse the trend
+        assert_eq!(builder.push(11.0, 1.0, 3).unwrap(), 0);
+        // Two boxes from the last close does
+        assert_eq!(builder.push(10.0, 1.0, 4).unwrap(), 1);
+        assert_eq!(builder.push(13.0, 1.0, 5).unwrap(), 2);
+
+        let series = builder.series();
+        assert_eq!(
+            bricks(series),
+            vec![
+                (10.0, 11.0),
+                (11.0, 12.0),
+                (11.0, 10.0),
+                (11.0, 12.0),
+                (12.0, 13.0)
+            ]
+        );
+        assert_eq!(series.volumes().as_slice(), &[3.0, 0.0, 2.0, 1.0, 0.0]);
+        assert_eq!(series.timestamps().as_slice(), &[2, 2, 4, 5, 5]);
+        assert_eq!(series.get_owned(2).unwrap().high, 11.0);
+    }
+
+    #[test]
+    fn test_renko_from_candles() {
+        let mut candles = CandleSeries::new(60);
+        for (i, &close) in [10.0, 10.4, 11.1, 11.9, 9.8].iter().enumerate() {
+            candles.push(close, 1.0, i as u64 * 60).unwrap();
+        }
+
+        let series = renko(&candles, &BoxSize::Fixed(0.5)).unwrap();
+        assert_eq!(
+            bricks(&series),
+            vec![
+                (10.0, 10.5),
+                (10.5, 11.0),
+                (11.0, 11.5),
+                (11.0, 10.5),
+                (10.5, 10.0)
+            ]
+        );
+        assert_eq!(series.timeframe(), Timeframe::Irregular);
+    }
+
+    #[test]
+    fn test_renko_rejects_invalid_prices() {
+        let mut builder = RenkoBuilder::new(1.0);
+        assert!(builder.push(f64::INFINITY, 1.0, 0).is_err());
+        assert_eq!(builder.push(10.0, 1.0, 0).unwrap(), 0);
+        for price in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            assert!(matches!(
+                builder.push(price, 1.0, 1),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+
+        // A jump past the brick limit leaves the builder unchanged
+        builder.set_max_bars_per_push(3);
+        assert!(matches!(
+            builder.push(14.0, 1.0, 1),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(builder.push(1e12, 1.0, 1).is_err());
+        assert!(builder.series().is_empty());
+        assert_eq!(builder.push(13.0, 1.0, 1).unwrap(), 3);
+        assert_eq!(builder.series().volumes().as_slice(), &[2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "box size must be positive and finite")]
+    fn test_renko_invalid_box_size() {
+        RenkoBuilder::new(f64::NAN);
+    }
+
+    #[test]
+    fn test_renko_rejects_invalid_box_size() {
+        let candles = CandleSeries::<f64>::new(60);
+        assert!(matches!(
+            renko(&candles, &BoxSize::Fixed(0.0)),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
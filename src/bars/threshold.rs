@@ -0,0 +1,419 @@
+//! Information-driven bars closed by tick count, volume or notional thresholds.
+
+use crate::core::{Candle, CandleSeries, Error, Numeric, Timeframe};
+
+use super::DEFAULT_MAX_BARS_PER_PUSH;
+
+/// Threshold that closes a bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarKind<T> {
+    /// Close a bar every `n` ticks.
+    Tick(usize),
+    /// Close a bar every time the traded volume reaches the threshold.
+    Volume(T),
+    /// Close a bar every time the traded notional (price × volume) reaches the threshold.
+    Dollar(T),
+}
+
+/// Builds tick, volume or dollar bars from a stream of trades.
+///
+/// Completed bars are stored in a [`CandleSeries`] with an irregular timeframe,
+/// timestamped with their first trade, so every indicator works on them unchanged.
+/// For volume and dollar bars, a trade larger than the space left in the current
+/// bar is split across as many bars as needed at the same price and timestamp,
+/// and the split parts add up to the trade's volume.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::bars::{BarBuilder, BarKind};
+///
+/// let mut bars = BarBuilder::new(BarKind::Volume(10.0));
+/// bars.push(100.0, 4.0, 0).unwrap();
+/// // This trade completes the first bar and fills two more
+/// assert_eq!(bars.push(101.0, 25.0, 1_000).unwrap(), 2);
+/// assert_eq!(bars.series().len(), 2);
+/// assert_eq!(bars.partial().unwrap().0.volume, 9.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BarBuilder<T> {
+    kind: BarKind<T>,
+    series: CandleSeries<T>,
+    current: Option<(Candle<T>, u64)>,
+    ticks: usize,
+    filled: T,
+    max_bars_per_push: usize,
+}
+
+impl<T: Numeric> BarBuilder<T> {
+    /// Creates a new bar builder.
+    ///
+    /// # Panics
+    /// Panics if the threshold is not positive.
+    pub fn new(kind: BarKind<T>) -> Self {
+        Self::with_series(kind, CandleSeries::new(Timeframe::Irregular))
+    }
+
+    /// Creates a new bar builder that retains at most `max_len` completed bars.
+    ///
+    /// # Panics
+    /// Panics if the threshold is not positive or `max_len` is zero.
+    pub fn with_max_len(kind: BarKind<T>, max_len: usize) -> Self {
+        Self::with_series(
+            kind,
+            CandleSeries::with_max_len(Timeframe::Irregular, max_len),
+        )
+    }
+
+    fn with_series(kind: BarKind<T>, series: CandleSeries<T>) -> Self {
+        let positive = match kind {
+            BarKind::Tick(n) => n > 0,
+            BarKind::Volume(threshold) | BarKind::Dollar(threshold) => threshold.is_positive(),
+        };
+        if !positive {
+            panic!("bar threshold must be positive");
+        }
+
+        Self {
+            kind,
+            series,
+            current: None,
+            ticks: 0,
+            filled: T::ZERO,
+            max_bars_per_push: DEFAULT_MAX_BARS_PER_PUSH,
+        }
+    }
+
+    /// Returns the threshold that closes a bar.
+    pub fn kind(&self) -> BarKind<T> {
+        self.kind
+    }
+
+    /// Returns the completed bars.
+    pub fn series(&self) -> &CandleSeries<T> {
+        &self.series
+    }
+
+    /// Consumes the builder and returns the completed bars.
+    pub fn into_series(self) -> CandleSeries<T> {
+        self.series
+    }
+
+    /// Returns the in-progress bar and its start timestamp, if any.
+    pub fn partial(&self) -> Option<(Candle<T>, u64)> {
+        self.current
+    }
+
+    /// Returns the maximum number of bars a single trade may complete.
+    pub fn max_bars_per_push(&self) -> usize {
+        self.max_bars_per_push
+    }
+
+    /// Sets the maximum number of bars a single trade may complete.
+    ///
+    /// A trade that would complete more bars, such as one with an absurd volume,
+    /// is rejected by [`BarBuilder::push`]. Defaults to
+    /// [`DEFAULT_MAX_BARS_PER_PUSH`](super::DEFAULT_MAX_BARS_PER_PUSH).
+    pub fn set_max_bars_per_push(&mut self, max_bars_per_push: usize) {
+        self.max_bars_per_push = max_bars_per_push;
+    }
+
+    /// Pushes a trade and returns the number of bars it completed.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidInput` if the price, volume or notional is not finite,
+    /// the volume of a volume or dollar bar or the notional of a dollar bar is
+    /// negative, or the trade would complete more than
+    /// [`BarBuilder::max_bars_per_push`] bars.
+    /// The builder is left unchanged on error.
+    pub fn push(&mut self, price: T, vol: T, ts: u64) -> Result<usize, Error> {
+        if !price.is_finite() || !vol.is_finite() {
+            return Err(Error::InvalidInput(format!(
+                "trade price ({price:?}) and volume ({vol:?}) must be finite"
+            )));
+        }
+
+        match self.kind {
+            BarKind::Tick(n) => {
+                self.add(price, vol, ts);
+                self.ticks += 1;
+                if self.ticks == n {
+                    self.close();
+                    return Ok(1);
+                }
+                Ok(0)
+            }
+            BarKind::Volume(threshold) => {
+                ensure_not_negative("volume", vol)?;
+                self.push_split(price, vol, ts, threshold, vol)
+            }
+            BarKind::Dollar(threshold) => {
+                ensure_not_negative("volume", vol)?;
+                let notional = price * vol;
+                if !notional.is_finite() {
+                    return Err(Error::InvalidInput(format!(
+                        "trade notional ({notional:?}) must be finite"
+                    )));
+                }
+                ensure_not_negative("notional", notional)?;
+                self.push_split(price, vol, ts, threshold, notional)
+            }
+        }
+    }
+
+    /// Distributes a trade measuring `amount` across bars of size `threshold`.
+    fn push_split(
+        &mut self,
+        price: T,
+        vol: T,
+        ts: u64,
+        threshold: T,
+        amount: T,
+    ) -> Result<usize, Error> {
+        let bars = self.count_split(threshold, amount);
+        if bars > self.max_bars_per_push {
+            return Err(Error::InvalidInput(format!(
+                "trade of {amount:?} would complete more than {} bars",
+                self.max_bars_per_push
+            )));
+        }
+
+        let mut completed = 0;
+        let mut remaining = amount;
+        let mut remaining_vol = vol;
+
+        loop {
+            let space = threshold - self.filled;
+            if remaining < space {
+                self.add(price, remaining_vol, ts);
+                self.filled += remaining;
+                return Ok(completed);
+            }
+
+            // Fill the current bar with the part of the trade that fits. Volume bars
+            // take exactly the space left and the last part takes whatever volume
+            // remains, so no volume is lost to rounding.
+            let part_vol = if amount.is_zero() || !(remaining - space).is_positive() {
+                remaining_vol
+            } else if matches!(self.kind, BarKind::Volume(_)) {
+                space
+            } else {
+                vol * (space / amount)
+            };
+            self.add(price, part_vol, ts);
+            self.close();
+            completed += 1;
+
+            remaining -= space;
+            remaining_vol -= part_vol;
+            if !remaining.is_positive() {
+                return Ok(completed);
+            }
+        }
+    }
+
+    /// Counts the bars a trade measuring `amount` would complete, stopping once it
+    /// exceeds the per-push limit.
+    fn count_split(&self, threshold: T, amount: T) -> usize {
+        let mut bars = 0;
+        let mut remaining = amount;
+        let mut space = threshold - self.filled;
+        while remaining >= space && bars <= self.max_bars_per_push {
+            bars += 1;
+            remaining -= space;
+            if !remaining.is_positive() {
+                break;
+            }
+            space = threshold;
+        }
+        bars
+    }
+
+    /// Adds a trade to the in-progress bar, opening one if needed.
+    fn add(&mut self, price: T, vol: T, ts: u64) {
+        match &mut self.current {
+            None => {
+                let candle = Candle {
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    volume: vol,
+                };
+                self.current = Some((candle, ts));
+            }
+            Some((candle, _)) => {
+                if price > candle.high {
+                    candle.high = price;
+                }
+                if price < candle.low {
+                    candle.low = price;
+                }
+                candle.close = price;
+                candle.volume += vol;
+            }
+        }
+    }
+
+    /// Moves the in-progress bar into the series.
+    fn close(&mut self) {
+        if let Some((candle, ts)) = self.current.take() {
+            self.series.push_candle_unchecked(candle, ts);
+        }
+        self.ticks = 0;
+        self.filled = T::ZERO;
+    }
+}
+
+/// Rejects a negative amount, which would let a bar exceed its threshold.
+fn ensure_not_negative<T: Numeric>(name: &str, amount: T) -> Result<(), Error> {
+    if amount < T::ZERO {
+        return Err(Error::InvalidInput(format!(
+            "trade {name} ({amount:?}) must not be negative"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Fixed;
+
+    #[test]
+    fn test_tick_bars() {
+        let mut bars = BarBuilder::new(BarKind::Tick(3));
+        let prices = [10.0, 12.0, 9.0, 11.0, 13.0, 12.0, 14.0];
+        let completed: usize = prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| bars.push(price, 1.0, i as u64).unwrap())
+            .sum();
+
+        assert_eq!(completed, 2);
+        let series = bars.series();
+        assert_eq!(series.timeframe(), Timeframe::Irregular);
+        assert_eq!(
+            series.get_owned(0).unwrap(),
+            Candle {
+                open: 10.0,
+                high: 12.0,
+                low: 9.0,
+                close: 9.0,
+                volume: 3.0,
+            }
+        );
+        assert_eq!(series.timestamps().as_slice(), &[0, 3]);
+        assert_eq!(
+            bars.partial(),
+            Some((
+                Candle {
+                    open: 14.0,
+                    high: 14.0,
+                    low: 14.0,
+                    close: 14.0,
+                    volume: 1.0
+                },
+                6
+            ))
+        );
+    }
+
+    #[test]
+    fn test_volume_bars_split_large_trade() {
+        let mut bars = BarBuilder::new(BarKind::Volume(10.0));
+        assert_eq!(bars.push(100.0, 4.0, 0).unwrap(), 0);
+        assert_eq!(bars.push(101.0, 26.0, 1).unwrap(), 3);
+        assert!(bars.partial().is_none());
+
+        let volumes: Vec<f64> = bars.series().volumes().iter().copied().collect();
+        assert_eq!(volumes, vec![10.0, 10.0, 10.0]);
+        assert_eq!(bars.series().get_owned(0).unwrap().close, 101.0);
+        assert_eq!(bars.series().timestamps().as_slice(), &[0, 1, 1]);
+    }
+
+    #[test]
+    fn test_dollar_bars() {
+        let mut bars = BarBuilder::new(BarKind::Dollar(1_000.0));
+        assert_eq!(bars.push(10.0, 50.0, 0).unwrap(), 0);
+        // 2_000 notional: 500 completes the first bar, 1_000 fills a second
+        assert_eq!(bars.push(20.0, 100.0, 1).unwrap(), 2);
+
+        let series = bars.series();
+        assert_eq!(series.volumes().as_slice(), &[75.0, 50.0]);
+        let (partial, ts) = bars.partial().unwrap();
+        assert_eq!(partial.volume, 25.0);
+        assert_eq!(ts, 1);
+    }
+
+    #[test]
+    fn test_split_conserves_volume() {
+        let mut bars = BarBuilder::new(BarKind::Volume(Fixed::<4>::ONE));
+        assert_eq!(bars.push(Fixed::ONE, Fixed::from_int(3), 0).unwrap(), 3);
+        assert_eq!(bars.series().volumes().as_slice(), &[Fixed::ONE; 3]);
+
+        // The notional splits into thirds, which do not add up exactly
+        let mut bars = BarBuilder::new(BarKind::Dollar(Fixed::<4>::from_int(3)));
+        assert_eq!(bars.push(Fixed::from_int(9), Fixed::ONE, 0).unwrap(), 3);
+        let total = bars
+            .series()
+            .volumes()
+            .iter()
+            .fold(Fixed::ZERO, |total, &volume| total + volume);
+        assert_eq!(total, Fixed::ONE);
+    }
+
+    #[test]
+    fn test_rejects_invalid_trades() {
+        let mut bars = BarBuilder::new(BarKind::Volume(1.0));
+        for (price, vol) in [(f64::NAN, 1.0), (1.0, f64::INFINITY), (f64::INFINITY, 1.0)] {
+            assert!(matches!(
+                bars.push(price, vol, 0),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+        let mut dollars = BarBuilder::new(BarKind::Dollar(1.0));
+        assert!(dollars.push(f64::MAX, 2.0, 0).is_err());
+
+        // A trade completing more bars than allowed leaves the builder unchanged
+        bars.set_max_bars_per_push(3);
+        bars.push(1.0, 0.5, 0).unwrap();
+        assert!(matches!(
+            bars.push(1.0, 3.5, 1),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(bars.series().is_empty());
+        assert_eq!(bars.partial().unwrap().0.volume, 0.5);
+        assert_eq!(bars.push(1.0, 3.0, 1).unwrap(), 3);
+        assert!(bars.push(1.0, 1e12, 2).is_err());
+    }
+
+    #[test]
+    fn test_rejects_negative_amounts() {
+        let mut bars = BarBuilder::new(BarKind::Volume(10.0));
+        bars.push(100.0, 4.0, 0).unwrap();
+        assert!(matches!(
+            bars.push(100.0, -4.0, 1),
+            Err(Error::InvalidInput(_))
+        ));
+        assert_eq!(bars.partial().unwrap().0.volume, 4.0);
+
+        let mut dollars = BarBuilder::new(BarKind::Dollar(1_000.0));
+        for (price, vol) in [(-10.0, 5.0), (10.0, -5.0), (-10.0, -5.0)] {
+            assert!(matches!(
+                dollars.push(price, vol, 0),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+        assert!(dollars.partial().is_none());
+
+        // Tick bars only count trades
+        let mut ticks = BarBuilder::new(BarKind::Tick(2));
+        assert_eq!(ticks.push(-1.0, -1.0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "bar threshold must be positive")]
+    fn test_invalid_threshold() {
+        BarBuilder::new(BarKind::Volume(0.0));
+    }
+}
@@ -0,0 +1,111 @@
+//! Indicator outputs tagged with the input bars they cover.
+
+use std::ops::Deref;
+
+use super::MaskedColumn;
+
+/// Output of a windowed computation tagged with the input bar it starts at.
+///
+/// Indicators only look at the last `max_history` bars of a series, so their
+/// output can be shorter than the input. Element `i` of the output belongs to
+/// input bar `start + i`; [`Aligned::get_bar`] looks values up by input bar index
+/// instead. The wrapped output is reachable through `Deref`.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::core::{Aligned, Column, MaskedColumn};
+///
+/// let values = MaskedColumn::with_warmup(Column::from(vec![1.0, 2.0, 3.0]), 1);
+/// let aligned = Aligned::new(values, 5, 300);
+///
+/// assert_eq!(aligned.start(), 5);
+/// assert_eq!(aligned.get(1), Some(2.0));
+/// assert_eq!(aligned.get_bar(6), Some(2.0));
+/// assert_eq!(aligned.get_bar(1), None);
+/// assert_eq!(aligned.end(), 8);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aligned<C> {
+    inner: C,
+    start: usize,
+    start_ts: u64,
+}
+
+impl<C> Aligned<C> {
+    /// Tags `inner` as starting at input bar `start`, whose timestamp is `start_ts`.
+    pub fn new(inner: C, start: usize, start_ts: u64) -> Self {
+        Self {
+            inner,
+            start,
+            start_ts,
+        }
+    }
+
+    /// Returns the index of the input bar the first output value belongs to.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the timestamp of the input bar the first output value belongs to.
+    pub fn start_timestamp(&self) -> u64 {
+        self.start_ts
+    }
+
+    /// Returns the wrapped output.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwraps the output, discarding the alignment.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Transforms the wrapped output, keeping the alignment.
+    pub fn map<D, F>(self, f: F) -> Aligned<D>
+    where
+        F: FnOnce(C) -> D,
+    {
+        Aligned {
+            inner: f(self.inner),
+            start: self.start,
+            start_ts: self.start_ts,
+        }
+    }
+}
+
+impl<T> Aligned<MaskedColumn<T>> {
+    /// Returns the index one past the last input bar covered by the output.
+    pub fn end(&self) -> usize {
+        self.start + self.inner.len()
+    }
+}
+
+impl<T: Copy> Aligned<MaskedColumn<T>> {
+    /// Gets the value for input bar `bar`, or None if the bar is not covered
+    /// by the output or its value is masked.
+    pub fn get_bar(&self, bar: usize) -> Option<T> {
+        bar.checked_sub(self.start)
+            .and_then(|index| self.inner.get(index))
+    }
+
+    /// Returns the input bar index of the first valid element, or None if there is none.
+    pub fn first_valid_bar(&self) -> Option<usize> {
+        self.inner.first_valid().map(|index| self.start + index)
+    }
+
+    /// Returns an iterator over the input bar indices and values of valid elements.
+    pub fn valid_bars(&self) -> impl Iterator<Item = (usize, T)> + '_ {
+        self.inner
+            .valid()
+            .map(|(index, value)| (self.start + index, value))
+    }
+}
+
+impl<C> Deref for Aligned<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
@@ -1,9 +1,13 @@
 //! OHLCV candle data structures and operations.
 
 use crate::core::Error;
-use std::fmt::Display;
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt::Display,
+    ops::Range,
+};
 
-use super::{Column, Numeric};
+use super::{CandleSeriesView, Column, Numeric, PartialBucket, Resampler, Timeframe};
 
 /// A single OHLCV candle with volume.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -25,7 +29,28 @@ pub struct CandleRef<'a, T> {
     pub volume: &'a T,
 }
 
+/// Policy applied when consecutive candles are more than one timeframe apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GapPolicy {
+    /// Leave missing periods out of the series.
+    #[default]
+    Ignore,
+    /// Insert flat candles at the previous close with zero volume for each missing period.
+    ForwardFill,
+    /// Reject the tick with `Error::MissingCandles`.
+    Error,
+}
+
 /// Time series of OHLCV candles for technical analysis.
+///
+/// A series created with [`CandleSeries::with_max_len`] keeps at most `max_len`
+/// candles: adding a candle beyond the bound evicts the oldest one from every
+/// column at once, so memory stays flat for long-running processes.
+///
+/// Periods without ticks are handled according to the series' [`GapPolicy`].
+///
+/// Ticks arriving after their candle has been superseded can still revise one of
+/// the last `late_window` candles; see [`CandleSeries::set_late_window`].
 #[derive(Debug, Clone)]
 pub struct CandleSeries<T> {
     opens: Column<T>,
@@ -33,24 +58,149 @@ pub struct CandleSeries<T> {
     lows: Column<T>,
     closes: Column<T>,
     volumes: Column<T>,
-    timestamps: Vec<u64>,
-    timeframe: u64,
+    timestamps: Column<u64>,
+    timeframe: Timeframe,
+    gap_policy: GapPolicy,
+    late_window: usize,
+    max_fill: usize,
+    /// Start and first and latest tick timestamps of the most recent candles, where
+    /// forward-filled candles have no tick yet.
+    recent_ticks: VecDeque<(u64, Option<(u64, u64)>)>,
+    /// Start timestamps of candles revised by late ticks.
+    revisions: BTreeSet<u64>,
+    /// Whether revisions were left out of the report because it was full.
+    revisions_truncated: bool,
 }
 
 impl<T: Numeric> CandleSeries<T> {
+    /// Maximum number of revisions reported by [`CandleSeries::take_revisions`];
+    /// the latest revisions are left out beyond it.
+    pub const MAX_REVISIONS: usize = 1024;
+
+    /// Default maximum number of candles inserted to fill a single gap.
+    pub const DEFAULT_MAX_FILL: usize = 10_000;
+
     /// Creates a new candle series with specified timeframe.
-    pub fn new(timeframe: u64) -> Self {
+    ///
+    /// # Panics
+    /// Panics if the timeframe is empty.
+    pub fn new(timeframe: impl Into<Timeframe>) -> Self {
+        let timeframe = valid_timeframe(timeframe.into());
         Self {
             opens: Column::new(),
             highs: Column::new(),
             lows: Column::new(),
             closes: Column::new(),
             volumes: Column::new(),
-            timestamps: Vec::new(),
+            timestamps: Column::new(),
+            timeframe,
+            gap_policy: GapPolicy::default(),
+            late_window: 0,
+            max_fill: Self::DEFAULT_MAX_FILL,
+            recent_ticks: VecDeque::new(),
+            revisions: BTreeSet::new(),
+            revisions_truncated: false,
+        }
+    }
+
+    /// Creates a new candle series with specified timeframe that retains at most `max_len` candles.
+    ///
+    /// # Panics
+    /// Panics if `max_len` is zero or the timeframe is empty.
+    pub fn with_max_len(timeframe: impl Into<Timeframe>, max_len: usize) -> Self {
+        let timeframe = valid_timeframe(timeframe.into());
+        Self {
+            opens: Column::with_max_len(max_len),
+            highs: Column::with_max_len(max_len),
+            lows: Column::with_max_len(max_len),
+            closes: Column::with_max_len(max_len),
+            volumes: Column::with_max_len(max_len),
+            timestamps: Column::with_max_len(max_len),
             timeframe,
+            gap_policy: GapPolicy::default(),
+            late_window: 0,
+            max_fill: Self::DEFAULT_MAX_FILL,
+            recent_ticks: VecDeque::new(),
+            revisions: BTreeSet::new(),
+            revisions_truncated: false,
         }
     }
 
+    /// Returns the maximum number of candles retained, or None if unbounded.
+    pub fn max_len(&self) -> Option<usize> {
+        self.timestamps.max_len()
+    }
+
+    /// Returns the timeframe of each candle.
+    pub fn timeframe(&self) -> Timeframe {
+        self.timeframe
+    }
+
+    /// Returns the policy applied to missing candle periods.
+    pub fn gap_policy(&self) -> GapPolicy {
+        self.gap_policy
+    }
+
+    /// Sets the policy applied to missing candle periods by subsequent pushes.
+    pub fn set_gap_policy(&mut self, policy: GapPolicy) {
+        self.gap_policy = policy;
+    }
+
+    /// Returns the maximum number of candles inserted to fill a single gap.
+    pub fn max_fill(&self) -> usize {
+        self.max_fill
+    }
+
+    /// Sets the maximum number of candles inserted to fill a single gap.
+    ///
+    /// A gap of more missing periods, such as one caused by a far-future timestamp,
+    /// is never filled: [`CandleSeries::push`] and [`CandleSeries::fill_gaps`] reject
+    /// it with `Error::MissingCandles` and [`CandleSeries::push_unchecked`] leaves it
+    /// in place. Defaults to [`CandleSeries::DEFAULT_MAX_FILL`].
+    pub fn set_max_fill(&mut self, max_fill: usize) {
+        self.max_fill = max_fill;
+    }
+
+    /// Returns the number of previous candles that late ticks may still revise.
+    pub fn late_window(&self) -> usize {
+        self.late_window
+    }
+
+    /// Sets the number of previous candles that late ticks may still revise.
+    ///
+    /// A tick whose candle is one of the last `late_window` candles before the
+    /// current one updates that candle's high, low and volume. Its close is only
+    /// updated if the tick is not older than the latest tick already applied to
+    /// that candle, and its open only if the tick is older than the first one. A
+    /// forward-filled candle has no ticks, so the first late tick
+    /// replaces its synthetic prices and volume. Revised candles are reported by
+    /// [`CandleSeries::take_revisions`].
+    ///
+    /// Tick ordering is only tracked for the last `late_window + 1` candles, so the
+    /// window should be set before ticks are pushed.
+    pub fn set_late_window(&mut self, late_window: usize) {
+        self.late_window = late_window;
+    }
+
+    /// Returns the start timestamps of candles revised by late ticks since the
+    /// last call in ascending order, and clears the report.
+    ///
+    /// Each candle is reported once. At most [`CandleSeries::MAX_REVISIONS`] of the
+    /// earliest candles are reported if the report is not drained in time; see
+    /// [`CandleSeries::revisions_truncated`].
+    pub fn take_revisions(&mut self) -> Vec<u64> {
+        self.revisions_truncated = false;
+        std::mem::take(&mut self.revisions).into_iter().collect()
+    }
+
+    /// Returns true if revisions were left out of the report because it was full.
+    ///
+    /// Only later candles are left out, so every candle after the last one returned
+    /// by the next [`CandleSeries::take_revisions`] should then be treated as revised.
+    pub fn revisions_truncated(&self) -> bool {
+        self.revisions_truncated
+    }
+
     /// Returns a reference to the opening prices column.
     pub fn opens(&self) -> &Column<T> {
         &self.opens
@@ -77,7 +227,7 @@ impl<T: Numeric> CandleSeries<T> {
     }
 
     /// Gets a candle at the specified index as a reference.
-    pub fn get(&self, index: usize) -> Option<CandleRef<T>> {
+    pub fn get(&self, index: usize) -> Option<CandleRef<'_, T>> {
         if index >= self.len() {
             return None;
         }
@@ -104,6 +254,34 @@ impl<T: Numeric> CandleSeries<T> {
         })
     }
 
+    /// Returns the start timestamp of each candle.
+    pub fn timestamps(&self) -> &Column<u64> {
+        &self.timestamps
+    }
+
+    /// Returns the index of the candle starting exactly at `ts`.
+    ///
+    /// See [`CandleSeriesView::index_of`] for how shared start timestamps are
+    /// resolved.
+    pub fn index_of(&self, ts: u64) -> Option<usize> {
+        self.view().index_of(ts)
+    }
+
+    /// Gets the candle whose period contains `ts`, using binary search.
+    pub fn get_at(&self, ts: u64) -> Option<CandleRef<'_, T>> {
+        self.view().get_at(ts)
+    }
+
+    /// Returns a zero-copy view over the whole series.
+    pub fn view(&self) -> CandleSeriesView<'_, T> {
+        self.into()
+    }
+
+    /// Returns a zero-copy view of the candles starting within `range`.
+    pub fn slice(&self, range: Range<u64>) -> CandleSeriesView<'_, T> {
+        self.view().slice(range)
+    }
+
     /// Returns the number of candles in the series.
     pub fn len(&self) -> usize {
         self.timestamps.len()
@@ -115,9 +293,11 @@ impl<T: Numeric> CandleSeries<T> {
     }
 
     /// Pushes a new price tick to the series, creating or updating candles based on timeframe.
-    /// Returns an error if the timestamp is out of order.
+    /// Returns an error if the timestamp is out of order, if periods were skipped
+    /// and the gap policy is `GapPolicy::Error`, or if more than `max_fill` periods
+    /// were skipped and the gap policy is `GapPolicy::ForwardFill`.
     pub fn push(&mut self, price: T, vol: T, ts: u64) -> Result<(), Error> {
-        let next_start = ts - (ts % self.timeframe);
+        let next_start = self.timeframe.bucket_start(ts);
 
         match self.timestamps.last() {
             None => {
@@ -127,27 +307,33 @@ impl<T: Numeric> CandleSeries<T> {
                 match next_start.cmp(&last_ts) {
                     // Push a new candle if the next start time is after the last candle start
                     std::cmp::Ordering::Greater => {
+                        self.handle_gap(last_ts, next_start)?;
                         self.push_new_candle(price, vol, next_start);
                     }
                     // Update the last candle if the next start time is same as the last candle start
                     std::cmp::Ordering::Equal => {
                         self.update_last_candle(price, vol);
                     }
-                    // If the next start time is before the last candle start, return an error
+                    // If the next start time is before the last candle start, revise it if it
+                    // is within the late window, otherwise return an error
                     std::cmp::Ordering::Less => {
-                        return Err(Error::InvalidTimestamp(next_start));
+                        if !self.update_late_candle(price, vol, ts, next_start) {
+                            return Err(Error::InvalidTimestamp(next_start));
+                        }
                     }
                 }
             }
         }
 
+        self.record_tick(next_start, ts);
         Ok(())
     }
 
     /// Pushes a new price tick without timestamp validation.
-    /// Ignores out-of-order timestamps instead of returning errors.
+    /// Ignores out-of-order timestamps instead of returning errors, and leaves gaps
+    /// in place when the gap policy is `GapPolicy::Error` or the gap is longer than `max_fill`.
     pub fn push_unchecked(&mut self, price: T, vol: T, ts: u64) {
-        let next_start = ts - (ts % self.timeframe);
+        let next_start = self.timeframe.bucket_start(ts);
 
         match self.timestamps.last() {
             None => {
@@ -157,17 +343,28 @@ impl<T: Numeric> CandleSeries<T> {
                 match next_start.cmp(&last_ts) {
                     // Push a new candle if the next start time is after the last candle start
                     std::cmp::Ordering::Greater => {
+                        if self.gap_policy == GapPolicy::ForwardFill {
+                            // Gaps longer than max_fill are left in place
+                            let _ = self.forward_fill(last_ts, next_start);
+                        }
                         self.push_new_candle(price, vol, next_start);
                     }
                     // Update the last candle if the next start time is same as the last candle start
                     std::cmp::Ordering::Equal => {
                         self.update_last_candle(price, vol);
                     }
-                    // If the next start time is before the last candle start, we ignore it
-                    std::cmp::Ordering::Less => {}
+                    // If the next start time is before the last candle start, revise it if it
+                    // is within the late window, otherwise we ignore it
+                    std::cmp::Ordering::Less => {
+                        if !self.update_late_candle(price, vol, ts, next_start) {
+                            return;
+                        }
+                    }
                 }
             }
         }
+
+        self.record_tick(next_start, ts);
     }
 
     /// Pushes a complete candle to the series without any validation.
@@ -180,6 +377,80 @@ impl<T: Numeric> CandleSeries<T> {
         self.timestamps.push(ts);
     }
 
+    /// Forward-fills every missing period in the series with flat candles at the
+    /// previous close and zero volume, regardless of the gap policy.
+    /// Returns the number of candles inserted.
+    ///
+    /// This is useful for series built with `GapPolicy::Ignore` or with
+    /// `push_candle_unchecked`. If the series is bounded, the oldest candles are
+    /// evicted as usual.
+    ///
+    /// # Errors
+    /// Returns `Error::MissingCandles` if a gap spans more than `max_fill` periods,
+    /// leaving the series unchanged.
+    pub fn fill_gaps(&mut self) -> Result<usize, Error> {
+        let mut filled = match self.max_len() {
+            Some(max_len) => Self::with_max_len(self.timeframe, max_len),
+            None => Self::new(self.timeframe),
+        };
+        filled.max_fill = self.max_fill;
+        filled.late_window = self.late_window;
+
+        let mut inserted = 0;
+        for i in 0..self.len() {
+            let ts = self.timestamps[i];
+            if let Some(&last_ts) = filled.timestamps.last() {
+                inserted += filled.forward_fill(last_ts, ts)?;
+            }
+            filled.push_candle_unchecked(self.get_owned(i).unwrap(), ts);
+        }
+
+        // Only the candle data is rebuilt; policies and tick tracking are kept, and
+        // inserted candles within the late window are tracked as forward-filled
+        let mut recent: Vec<_> = self.recent_ticks.drain(..).collect();
+        recent.extend(filled.recent_ticks);
+        recent.sort_by_key(|&(start, _)| start);
+        let skip = recent.len().saturating_sub(self.late_window + 1);
+        self.recent_ticks = recent.into_iter().skip(skip).collect();
+
+        self.opens = filled.opens;
+        self.highs = filled.highs;
+        self.lows = filled.lows;
+        self.closes = filled.closes;
+        self.volumes = filled.volumes;
+        self.timestamps = filled.timestamps;
+        Ok(inserted)
+    }
+
+    /// Resamples this series into a higher `timeframe`.
+    ///
+    /// The trailing bucket is complete only if the last candle covers its end;
+    /// otherwise it is kept or dropped according to `partial`.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidTimeframe` if `timeframe` is not a multiple of this series' timeframe.
+    pub fn resample(
+        &self,
+        timeframe: impl Into<Timeframe>,
+        partial: PartialBucket,
+    ) -> Result<CandleSeries<T>, Error> {
+        let mut resampler = Resampler::new(self.timeframe, timeframe)?;
+        let mut resampled = CandleSeries::new(resampler.timeframe());
+
+        for i in 0..self.len() {
+            let candle = self.get_owned(i).unwrap();
+            for (bucket, start) in resampler.push(candle, self.timestamps[i])? {
+                resampled.push_candle_unchecked(bucket, start);
+            }
+        }
+
+        if let (PartialBucket::Keep, Some((bucket, start))) = (partial, resampler.flush()) {
+            resampled.push_candle_unchecked(bucket, start);
+        }
+
+        Ok(resampled)
+    }
+
     /// Calculate the true range for each candle in this series.
     ///
     /// True range is defined as the maximum of:
@@ -187,31 +458,56 @@ impl<T: Numeric> CandleSeries<T> {
     /// - The absolute difference between the current candle's high and the previous candle's close.
     /// - The absolute difference between the current candle's low and the previous candle's close.
     ///
-    /// Because the first candle has no previous candle, its true range is simply the high minus the low.
+    /// Only the last `max_history` true ranges are kept. A window truncated by
+    /// `max_history` measures its first true range against the close before it; the
+    /// first candle of the series has no previous close, so its true range is simply
+    /// the high minus the low.
     pub fn true_range(&self, max_history: Option<usize>) -> Column<T> {
-        if self.is_empty() {
-            return Column::new();
+        self.view().true_range(max_history)
+    }
+
+    /// Applies the gap policy before a candle starting at `next_start` is pushed.
+    fn handle_gap(&mut self, last_ts: u64, next_start: u64) -> Result<(), Error> {
+        match self.gap_policy {
+            GapPolicy::Ignore => {}
+            GapPolicy::ForwardFill => {
+                self.forward_fill(last_ts, next_start)?;
+            }
+            GapPolicy::Error => {
+                if next_start > self.timeframe.next_start(last_ts) {
+                    return Err(Error::MissingCandles(last_ts, next_start));
+                }
+            }
         }
+        Ok(())
+    }
 
-        let len = self.len();
-        let start = match max_history {
-            Some(max) => len.saturating_sub(max),
-            None => 0,
+    /// Push flat candles at the last close for every period between `last_ts` and `next_start`.
+    /// Returns the number of candles pushed, or `Error::MissingCandles` without pushing
+    /// any if there are more than `max_fill` of them.
+    fn forward_fill(&mut self, last_ts: u64, next_start: u64) -> Result<usize, Error> {
+        let Some(&close) = self.closes.last() else {
+            return Ok(0);
         };
 
-        let mut tr = Column::with_capacity(len - start);
-        for i in start..len {
-            let candle = self.get(i).unwrap();
-            if i == 0 {
-                let simple_range = *candle.high - *candle.low;
-                tr.push(simple_range);
-            } else {
-                let prev_candle = self.get(i - 1).unwrap();
-                let range = candle.true_range(&prev_candle);
-                tr.push(range);
+        // Count first so that an oversized gap leaves the series untouched
+        let mut missing = 0;
+        let mut ts = self.timeframe.next_start(last_ts);
+        while ts < next_start {
+            if missing == self.max_fill {
+                return Err(Error::MissingCandles(last_ts, next_start));
             }
+            ts = self.timeframe.next_start(ts);
+            missing += 1;
         }
-        tr
+
+        let mut ts = self.timeframe.next_start(last_ts);
+        for _ in 0..missing {
+            self.push_new_candle(close, T::ZERO, ts);
+            self.track_candle(ts, None);
+            ts = self.timeframe.next_start(ts);
+        }
+        Ok(missing)
     }
 
     /// Push a new candle with the given price, volume, and start timestamp.
@@ -224,6 +520,95 @@ impl<T: Numeric> CandleSeries<T> {
         self.timestamps.push(start_ts);
     }
 
+    /// Applies a late tick to the candle starting at `start` if it is within the late window.
+    /// Returns false if the tick could not be applied.
+    fn update_late_candle(&mut self, price: T, vol: T, ts: u64, start: u64) -> bool {
+        let Ok(i) = self.timestamps.as_slice().binary_search(&start) else {
+            return false;
+        };
+        if i + self.late_window + 1 < self.len() {
+            return false;
+        }
+
+        let ticks = self
+            .recent_ticks
+            .iter()
+            .rev()
+            .find(|(s, _)| *s == start)
+            .map(|&(_, ticks)| ticks);
+        match ticks {
+            // The first tick of a forward-filled candle replaces its synthetic prices
+            Some(None) => {
+                self.opens[i] = price;
+                self.highs[i] = price;
+                self.lows[i] = price;
+                self.closes[i] = price;
+                self.volumes[i] = vol;
+            }
+            _ => {
+                if price > self.highs[i] {
+                    self.highs[i] = price;
+                }
+                if price < self.lows[i] {
+                    self.lows[i] = price;
+                }
+                self.volumes[i] += vol;
+
+                // Only a tick older than the first one applied may set the open, and
+                // only one at least as recent as the latest may set the close
+                if let Some(Some((first, latest))) = ticks {
+                    if ts < first {
+                        self.opens[i] = price;
+                    }
+                    if ts >= latest {
+                        self.closes[i] = price;
+                    }
+                }
+            }
+        }
+
+        // Keep the earliest revisions, which are where recomputation must start
+        self.revisions.insert(start);
+        if self.revisions.len() > Self::MAX_REVISIONS {
+            self.revisions.pop_last();
+            self.revisions_truncated = true;
+        }
+        true
+    }
+
+    /// Records a tick timestamp applied to the candle starting at `start`.
+    fn record_tick(&mut self, start: u64, ts: u64) {
+        // In-order ticks belong to the last candle, so search from the newest one
+        if let Some(entry) = self
+            .recent_ticks
+            .iter_mut()
+            .rev()
+            .find(|(s, _)| *s == start)
+        {
+            entry.1 = Some(
+                entry
+                    .1
+                    .map_or((ts, ts), |(first, latest)| (first.min(ts), latest.max(ts))),
+            );
+            return;
+        }
+        self.track_candle(start, Some(ts));
+    }
+
+    /// Starts tracking the ticks of a new last candle starting at `start`.
+    fn track_candle(&mut self, start: u64, tick: Option<u64>) {
+        // Candles without tick history, such as those pushed unchecked, are not tracked
+        if self.recent_ticks.back().is_some_and(|&(s, _)| s > start) {
+            return;
+        }
+
+        self.recent_ticks
+            .push_back((start, tick.map(|ts| (ts, ts))));
+        while self.recent_ticks.len() > self.late_window + 1 {
+            self.recent_ticks.pop_front();
+        }
+    }
+
     fn update_last_candle(&mut self, price: T, vol: T) {
         let i = self.len() - 1;
         if price > self.highs[i] {
@@ -237,6 +622,14 @@ impl<T: Numeric> CandleSeries<T> {
     }
 }
 
+/// Returns `timeframe`, panicking if it cannot bucket timestamps.
+pub(crate) fn valid_timeframe(timeframe: Timeframe) -> Timeframe {
+    if timeframe.is_empty() {
+        panic!("timeframe length must be greater than zero");
+    }
+    timeframe
+}
+
 impl<T: Numeric> Candle<T> {
     /// Calculates the true range between this candle and the previous candle.
     pub fn true_range(&self, prev: &Candle<T>) -> T {
@@ -386,3 +779,295 @@ impl<T: Numeric + Display> Display for CandleSeries<T> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_series_evicts_oldest() {
+        let mut candles = CandleSeries::with_max_len(60, 3);
+        for i in 0..10u64 {
+            candles.push(i as f64, 1.0, i * 60).unwrap();
+            candles.push(i as f64 + 0.5, 1.0, i * 60 + 30).unwrap();
+        }
+
+        assert_eq!(candles.len(), 3);
+        assert_eq!(candles.max_len(), Some(3));
+        for column in [
+            candles.opens(),
+            candles.highs(),
+            candles.lows(),
+            candles.closes(),
+            candles.volumes(),
+        ] {
+            assert_eq!(column.len(), 3);
+        }
+
+        let first = candles.get_owned(0).unwrap();
+        assert_eq!(first.open, 7.0);
+        assert_eq!(first.close, 7.5);
+        assert_eq!(first.volume, 2.0);
+        assert_eq!(candles.timestamps[0], 7 * 60);
+
+        // Out of order timestamps are still detected after eviction
+        assert!(candles.push(1.0, 1.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_gap_policy() {
+        let mut candles = CandleSeries::new(60);
+        candles.push(1.0, 1.0, 0).unwrap();
+        candles.push(2.0, 1.0, 180).unwrap();
+        assert_eq!(candles.len(), 2);
+
+        candles.set_gap_policy(GapPolicy::Error);
+        assert!(matches!(
+            candles.push(3.0, 1.0, 300),
+            Err(Error::MissingCandles(180, 300))
+        ));
+        candles.push_unchecked(3.0, 1.0, 300);
+        assert_eq!(candles.len(), 3);
+
+        candles.set_gap_policy(GapPolicy::ForwardFill);
+        candles.push(4.0, 1.0, 500).unwrap();
+        assert_eq!(candles.len(), 6);
+        assert_eq!(candles.timestamps[3], 360);
+        assert_eq!(
+            candles.get_owned(3).unwrap(),
+            Candle {
+                open: 3.0,
+                high: 3.0,
+                low: 3.0,
+                close: 3.0,
+                volume: 0.0,
+            }
+        );
+        assert_eq!(candles.timestamps[4], 420);
+        assert_eq!(candles.timestamps[5], 480);
+    }
+
+    #[test]
+    fn test_fill_gaps() {
+        let mut candles = CandleSeries::with_max_len(60, 4);
+        candles.push(1.0, 1.0, 0).unwrap();
+        candles.push(2.0, 1.0, 180).unwrap();
+
+        assert_eq!(candles.fill_gaps().unwrap(), 2);
+        assert_eq!(candles.len(), 4);
+        assert_eq!(candles.closes().as_slice(), &[1.0, 1.0, 1.0, 2.0]);
+        assert_eq!(candles.volumes().as_slice(), &[1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(candles.max_len(), Some(4));
+        assert_eq!(candles.fill_gaps().unwrap(), 0);
+
+        // Gaps longer than max_fill are rejected without touching the series
+        candles.push_candle_unchecked(candles.get_owned(3).unwrap(), 600);
+        candles.set_max_fill(5);
+        assert!(matches!(
+            candles.fill_gaps(),
+            Err(Error::MissingCandles(180, 600))
+        ));
+        assert_eq!(candles.len(), 4);
+        candles.set_max_fill(6);
+        assert_eq!(candles.fill_gaps().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_forward_fill_limit() {
+        let mut candles = CandleSeries::new(60);
+        candles.set_gap_policy(GapPolicy::ForwardFill);
+        candles.push(1.0, 1.0, 0).unwrap();
+
+        // A far-future tick is rejected instead of filling every missing period
+        let far = 60 * (CandleSeries::<f64>::DEFAULT_MAX_FILL as u64 + 2);
+        assert!(matches!(
+            candles.push(2.0, 1.0, far),
+            Err(Error::MissingCandles(0, _))
+        ));
+        assert_eq!(candles.len(), 1);
+
+        // The largest fillable gap is still filled
+        candles.set_max_fill(3);
+        assert!(candles.push(2.0, 1.0, 300).is_err());
+        candles.push(2.0, 1.0, 240).unwrap();
+        assert_eq!(candles.len(), 5);
+
+        // Unchecked pushes leave oversized gaps in place
+        candles.push_unchecked(3.0, 1.0, 600);
+        assert_eq!(candles.len(), 6);
+        assert_eq!(candles.timestamps[5], 600);
+    }
+
+    fn get_resample_data() -> CandleSeries<f64> {
+        let mut candles = CandleSeries::new(60);
+        // Seven one-minute candles: 1..=7 with high +1, low -1 and volume 10
+        for i in 0..7u64 {
+            let price = (i + 1) as f64;
+            candles.push_candle_unchecked(
+                Candle {
+                    open: price,
+                    high: price + 1.0,
+                    low: price - 1.0,
+                    close: price + 0.5,
+                    volume: 10.0,
+                },
+                i * 60,
+            );
+        }
+        candles
+    }
+
+    #[test]
+    fn test_resample() {
+        let candles = get_resample_data();
+
+        let resampled = candles.resample(180, PartialBucket::Keep).unwrap();
+        assert_eq!(resampled.len(), 3);
+        assert_eq!(resampled.timeframe(), Timeframe::millis(180));
+        assert_eq!(
+            resampled.get_owned(0).unwrap(),
+            Candle {
+                open: 1.0,
+                high: 4.0,
+                low: 0.0,
+                close: 3.5,
+                volume: 30.0,
+            }
+        );
+        assert_eq!(resampled.timestamps[1], 180);
+        assert_eq!(resampled.get_owned(2).unwrap().volume, 10.0);
+
+        let resampled = candles.resample(180, PartialBucket::Drop).unwrap();
+        assert_eq!(resampled.len(), 2);
+
+        // A trailing bucket completed by a single candle after a gap is kept
+        let mut gapped = CandleSeries::new(60);
+        gapped.push(1.0, 1.0, 0).unwrap();
+        gapped.push(2.0, 1.0, 300).unwrap();
+        let resampled = gapped.resample(180, PartialBucket::Drop).unwrap();
+        assert_eq!(resampled.timestamps().as_slice(), &[0, 180]);
+        assert_eq!(resampled.closes().as_slice(), &[1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_resample_invalid_timeframe() {
+        let candles = get_resample_data();
+        assert!(matches!(
+            candles.resample(90, PartialBucket::Keep),
+            Err(Error::InvalidTimeframe(Timeframe::Fixed { length: 90, .. }))
+        ));
+        let empty = Timeframe::Fixed {
+            length: 0,
+            offset: 0,
+        };
+        assert!(matches!(
+            candles.resample(empty, PartialBucket::Keep),
+            Err(Error::InvalidTimeframe(Timeframe::Fixed { length: 0, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_late_ticks() {
+        let mut candles = CandleSeries::new(60);
+        candles.push(10.0, 1.0, 10).unwrap();
+        candles.push(11.0, 1.0, 50).unwrap();
+        candles.push(12.0, 1.0, 70).unwrap();
+
+        // Late ticks are rejected by default
+        assert!(matches!(
+            candles.push(9.0, 1.0, 20),
+            Err(Error::InvalidTimestamp(0))
+        ));
+
+        candles.set_late_window(1);
+        candles.push(13.0, 1.0, 130).unwrap();
+        // Too old for the late window
+        assert!(candles.push(9.0, 1.0, 20).is_err());
+
+        // Older than the first tick in its candle: open is updated and close is kept
+        candles.push(14.0, 2.0, 65).unwrap();
+        // Newer than the latest tick in its candle: close is updated
+        candles.push(11.5, 1.0, 110).unwrap();
+
+        assert_eq!(
+            candles.get_owned(1).unwrap(),
+            Candle {
+                open: 14.0,
+                high: 14.0,
+                low: 11.5,
+                close: 11.5,
+                volume: 4.0,
+            }
+        );
+        assert_eq!(candles.take_revisions(), vec![60]);
+        assert!(candles.take_revisions().is_empty());
+
+        // Late ticks outside the window are still dropped by push_unchecked
+        candles.push_unchecked(100.0, 1.0, 0);
+        assert_eq!(candles.get_owned(0).unwrap().high, 11.0);
+        assert_eq!(candles.volumes().as_slice(), &[2.0, 4.0, 1.0]);
+
+        // Revisions are reported once per candle
+        candles.push(12.5, 1.0, 100).unwrap();
+        candles.push(12.6, 1.0, 105).unwrap();
+        assert_eq!(candles.take_revisions(), vec![60]);
+    }
+
+    #[test]
+    fn test_late_tick_in_filled_candle() {
+        let mut candles = CandleSeries::new(60);
+        candles.set_late_window(3);
+        candles.set_gap_policy(GapPolicy::ForwardFill);
+        candles.push(10.0, 1.0, 0).unwrap();
+        candles.push(12.0, 1.0, 180).unwrap();
+        assert_eq!(candles.len(), 4);
+
+        // The first tick of a filled candle replaces its synthetic prices
+        candles.push(11.0, 2.0, 70).unwrap();
+        candles.push(11.5, 1.0, 90).unwrap();
+        candles.push(10.8, 1.0, 80).unwrap();
+        assert_eq!(
+            candles.get_owned(1).unwrap(),
+            Candle {
+                open: 11.0,
+                high: 11.5,
+                low: 10.8,
+                close: 11.5,
+                volume: 4.0,
+            }
+        );
+
+        // The same holds for candles inserted by fill_gaps
+        let mut candles = CandleSeries::new(60);
+        candles.set_late_window(3);
+        candles.push(10.0, 1.0, 0).unwrap();
+        candles.push(12.0, 1.0, 180).unwrap();
+        assert_eq!(candles.fill_gaps().unwrap(), 2);
+        candles.push(11.0, 2.0, 130).unwrap();
+        assert_eq!(candles.get_owned(2).unwrap().open, 11.0);
+        assert_eq!(candles.get_owned(2).unwrap().close, 11.0);
+        assert_eq!(candles.take_revisions(), vec![120]);
+    }
+
+    #[test]
+    fn test_revisions_bounded() {
+        let max = CandleSeries::<f64>::MAX_REVISIONS as u64;
+        let mut candles = CandleSeries::new(60);
+        candles.set_late_window(max as usize + 10);
+        for i in 0..max + 11 {
+            candles.push(1.0, 1.0, i * 60).unwrap();
+        }
+        for i in 0..max + 10 {
+            candles.push(2.0, 1.0, i * 60 + 1).unwrap();
+        }
+
+        // Only the earliest revisions are kept until drained
+        assert!(candles.revisions_truncated());
+        let revisions = candles.take_revisions();
+        assert_eq!(revisions.len(), max as usize);
+        assert_eq!(revisions[0], 0);
+        assert_eq!(revisions[max as usize - 1], (max - 1) * 60);
+        assert!(!candles.revisions_truncated());
+        assert!(candles.take_revisions().is_empty());
+    }
+}
@@ -3,71 +3,152 @@
 use std::{
     fmt::Display,
     iter::Extend,
-    ops::{Index, IndexMut, Range, RangeFrom},
+    ops::{Add, Div, Index, IndexMut, Mul, Range, RangeFrom, Sub},
     vec::IntoIter,
 };
 
-use super::Numeric;
+use super::{Error, Numeric};
 
 /// Efficient column storage for numerical data with vectorized operations.
-#[derive(Debug, Clone, PartialEq, Default)]
+///
+/// Columns support element-wise arithmetic with scalars and with other columns.
+/// Combining two columns requires equal lengths and returns
+/// `Error::LengthMismatch` otherwise, rather than silently truncating:
+///
+/// ```rust
+/// use mizuhiki_ta::core::Column;
+///
+/// # fn main() -> Result<(), mizuhiki_ta::core::Error> {
+/// let close = Column::from(vec![10.0, 11.0, 12.0]);
+/// let ema = Column::from(vec![10.0, 10.5, 11.0]);
+///
+/// let spread = (&close - &ema)? / 2.0;
+/// assert_eq!(spread.as_slice(), &[0.0, 0.25, 0.5]);
+/// assert_eq!(spread.gt(0.0).as_slice(), &[false, true, true]);
+/// assert!((&close - &Column::from(vec![1.0])).is_err());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A column can optionally be bounded with [`Column::with_max_len`], in which case
+/// pushing beyond the bound evicts the oldest value. Evicted values are reclaimed
+/// lazily so that pushes stay amortized O(1), memory grows with use up to twice
+/// the bound and the live values are always available as one contiguous slice.
+#[derive(Debug, Clone)]
 pub struct Column<T> {
     raw: Vec<T>,
+    head: usize,
+    max_len: Option<usize>,
 }
 
-impl<T: Numeric> Column<T> {
+impl<T> Column<T> {
     /// Creates a new empty column.
     pub fn new() -> Self {
-        Self::default()
+        Self {
+            raw: Vec::new(),
+            head: 0,
+            max_len: None,
+        }
     }
 
     /// Creates a new column with the specified capacity.
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             raw: Vec::with_capacity(capacity),
+            head: 0,
+            max_len: None,
+        }
+    }
+
+    /// Creates a new column holding at most `max_len` values.
+    /// Once full, each push evicts the oldest value.
+    ///
+    /// No storage is allocated until values are pushed.
+    ///
+    /// # Panics
+    /// Panics if `max_len` is zero.
+    pub fn with_max_len(max_len: usize) -> Self {
+        if max_len == 0 {
+            panic!("max_len must be greater than zero");
+        }
+
+        Self {
+            raw: Vec::new(),
+            head: 0,
+            max_len: Some(max_len),
         }
     }
 
+    /// Returns the maximum number of values retained, or None if unbounded.
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
     /// Gets a reference to the element at the specified index.
     pub fn get(&self, index: usize) -> Option<&T> {
-        self.raw.get(index)
+        self.as_slice().get(index)
     }
 
     /// Pushes a value to the end of the column.
+    /// If the column is bounded and full, the oldest value is evicted.
     pub fn push(&mut self, value: T) {
+        if let Some(max_len) = self.max_len
+            && self.raw.len() == self.raw.capacity()
+        {
+            // Grow geometrically, but never past the point where evicted values are dropped
+            let limit = max_len.saturating_mul(2);
+            let capacity = self.raw.capacity().saturating_mul(2).max(4).min(limit);
+            self.raw
+                .reserve_exact(capacity.saturating_sub(self.raw.len()).max(1));
+        }
         self.raw.push(value);
+        if let Some(max_len) = self.max_len
+            && self.len() > max_len
+        {
+            self.evict(1);
+        }
     }
 
     /// Returns a reference to the last element, or None if empty.
     pub fn last(&self) -> Option<&T> {
-        self.raw.last()
+        self.as_slice().last()
     }
 
     /// Returns the number of elements in the column.
     pub fn len(&self) -> usize {
-        self.raw.len()
+        self.raw.len() - self.head
     }
 
     /// Returns the current capacity of the column.
     pub fn capacity(&self) -> usize {
-        self.raw.capacity()
+        self.raw.capacity() - self.head
     }
 
     /// Returns an iterator over the elements.
     pub fn iter(&self) -> std::slice::Iter<'_, T> {
-        self.raw.iter()
+        self.as_slice().iter()
     }
 
     /// Returns true if the column is empty.
     pub fn is_empty(&self) -> bool {
-        self.raw.is_empty()
+        self.len() == 0
+    }
+
+    /// Returns the elements as a contiguous slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.raw[self.head..]
+    }
+
+    /// Returns the elements as a contiguous mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.raw[self.head..]
     }
 
     /// Trims the column to the specified length, removing elements from the beginning.
     pub fn trim(&mut self, len: usize) {
         let current_len = self.len();
         if current_len > len {
-            self.raw.drain(0..current_len - len);
+            self.evict(current_len - len);
         }
     }
 
@@ -76,11 +157,13 @@ impl<T: Numeric> Column<T> {
     where
         F: FnMut(&T) -> bool,
     {
+        self.compact();
         self.raw.retain(|value| filter(value));
     }
 
     /// Shrinks the capacity of the column to match its length.
     pub fn shrink_to_fit(&mut self) {
+        self.compact();
         self.raw.shrink_to_fit();
     }
 
@@ -88,51 +171,67 @@ impl<T: Numeric> Column<T> {
     pub fn map<U, F>(&self, mut f: F) -> Column<U>
     where
         F: FnMut(&T) -> U,
-        U: Numeric,
     {
-        let mapped: Vec<U> = self.raw.iter().map(&mut f).collect();
+        let mapped: Vec<U> = self.iter().map(&mut f).collect();
         mapped.into()
     }
 
-    /// Calculates gains and losses for consecutive elements in the column.
-    /// Returns a tuple of (gains, losses) columns.
-    pub fn gains_losses(&self, max_history: Option<usize>) -> (Column<T>, Column<T>) {
-        if self.is_empty() {
-            return (Column::new(), Column::new());
+    /// Combines two columns of equal length element by element.
+    ///
+    /// # Errors
+    /// Returns `Error::LengthMismatch` if the columns have different lengths.
+    pub fn zip_with<U, V, F>(&self, other: &Column<U>, mut f: F) -> Result<Column<V>, Error>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        ensure_same_len(self.len(), other.len())?;
+        Ok(self
+            .iter()
+            .zip(other.iter())
+            .map(|(a, b)| f(a, b))
+            .collect())
+    }
+
+    /// Removes `count` elements from the beginning of the column.
+    ///
+    /// Evicted elements are only dropped once they outnumber the live elements,
+    /// which keeps eviction amortized O(1).
+    fn evict(&mut self, count: usize) {
+        self.head += count;
+        if self.head >= self.len() {
+            self.compact();
         }
+    }
 
-        let len = self.len();
-        let start = match max_history {
-            Some(max) => len.saturating_sub(max),
-            None => 0,
-        };
-
-        let capacity = len - start;
-        let mut gains = Column::with_capacity(capacity);
-        let mut losses = Column::with_capacity(capacity);
-
-        for i in start..len {
-            let change = self[i] - self[i.saturating_sub(1)];
-            if change.is_positive() {
-                gains.push(change);
-                losses.push(T::ZERO);
-            } else {
-                gains.push(T::ZERO);
-                losses.push(change.abs());
-            }
+    /// Drops evicted elements so that the live elements start at the beginning of storage.
+    fn compact(&mut self) {
+        if self.head > 0 {
+            self.raw.drain(..self.head);
+            self.head = 0;
         }
-        (gains, losses)
+    }
+}
+
+impl<T: Numeric> Column<T> {
+    /// Calculates gains and losses for consecutive elements in the column.
+    /// Returns a tuple of (gains, losses) columns.
+    pub fn gains_losses(&self, max_history: Option<usize>) -> (Column<T>, Column<T>) {
+        gains_losses(self.as_slice(), max_history)
     }
 
     /// Converts the column into an exponentially weighted moving average.
-    /// The alpha parameter controls the decay rate (0 < alpha < 1).
+    /// The alpha parameter controls the decay rate (0 < alpha <= 1);
+    /// an alpha of 1 returns the column unchanged.
+    ///
+    /// This is the [`EwmMode::Recursive`](super::EwmMode::Recursive) mode; see
+    /// [`Column::ewm_mean`] for adjusted and SMA-seeded weighting.
     pub fn into_ewm_mean(mut self, alpha: T) -> Column<T> {
         debug_assert!(
-            alpha > T::ZERO && alpha < T::ONE,
+            alpha > T::ZERO && alpha <= T::ONE,
             "Alpha must be between 0 and 1"
         );
 
-        if self.raw.is_empty() {
+        if self.is_empty() {
             return self;
         }
 
@@ -142,48 +241,209 @@ impl<T: Numeric> Column<T> {
 
         self
     }
+
+    /// Returns a mask of the elements greater than `value`.
+    pub fn gt(&self, value: T) -> Column<bool> {
+        self.map(|v| *v > value)
+    }
+
+    /// Returns a mask of the elements greater than or equal to `value`.
+    pub fn ge(&self, value: T) -> Column<bool> {
+        self.map(|v| *v >= value)
+    }
+
+    /// Returns a mask of the elements less than `value`.
+    pub fn lt(&self, value: T) -> Column<bool> {
+        self.map(|v| *v < value)
+    }
+
+    /// Returns a mask of the elements less than or equal to `value`.
+    pub fn le(&self, value: T) -> Column<bool> {
+        self.map(|v| *v <= value)
+    }
+
+    /// Returns a mask of the elements greater than the matching elements of `other`.
+    ///
+    /// # Errors
+    /// Returns `Error::LengthMismatch` if the columns have different lengths.
+    pub fn gt_column(&self, other: &Column<T>) -> Result<Column<bool>, Error> {
+        self.zip_with(other, |a, b| a > b)
+    }
+
+    /// Returns a mask of the elements greater than or equal to the matching elements of `other`.
+    ///
+    /// # Errors
+    /// Returns `Error::LengthMismatch` if the columns have different lengths.
+    pub fn ge_column(&self, other: &Column<T>) -> Result<Column<bool>, Error> {
+        self.zip_with(other, |a, b| a >= b)
+    }
+
+    /// Returns a mask of the elements less than the matching elements of `other`.
+    ///
+    /// # Errors
+    /// Returns `Error::LengthMismatch` if the columns have different lengths.
+    pub fn lt_column(&self, other: &Column<T>) -> Result<Column<bool>, Error> {
+        self.zip_with(other, |a, b| a < b)
+    }
+
+    /// Returns a mask of the elements less than or equal to the matching elements of `other`.
+    ///
+    /// # Errors
+    /// Returns `Error::LengthMismatch` if the columns have different lengths.
+    pub fn le_column(&self, other: &Column<T>) -> Result<Column<bool>, Error> {
+        self.zip_with(other, |a, b| a <= b)
+    }
 }
 
-impl<T: Numeric> Index<usize> for Column<T> {
+/// Returns `Error::LengthMismatch` if two lengths differ.
+fn ensure_same_len(left: usize, right: usize) -> Result<(), Error> {
+    if left != right {
+        return Err(Error::LengthMismatch(left, right));
+    }
+    Ok(())
+}
+
+/// Implements an arithmetic operator between columns and with scalars.
+///
+/// Column-column operations return a `Result` because the lengths may differ;
+/// scalar operations broadcast the scalar and cannot fail. Owned columns are
+/// updated in place.
+macro_rules! impl_column_op {
+    ($op:ident, $method:ident) => {
+        impl<T: Numeric> $op<&Column<T>> for &Column<T> {
+            type Output = Result<Column<T>, Error>;
+
+            fn $method(self, other: &Column<T>) -> Self::Output {
+                self.zip_with(other, |a, b| $op::$method(*a, *b))
+            }
+        }
+
+        impl<T: Numeric> $op<&Column<T>> for Column<T> {
+            type Output = Result<Column<T>, Error>;
+
+            fn $method(mut self, other: &Column<T>) -> Self::Output {
+                ensure_same_len(self.len(), other.len())?;
+                for (a, b) in self.as_mut_slice().iter_mut().zip(other.iter()) {
+                    *a = $op::$method(*a, *b);
+                }
+                Ok(self)
+            }
+        }
+
+        impl<T: Numeric> $op<T> for &Column<T> {
+            type Output = Column<T>;
+
+            fn $method(self, value: T) -> Self::Output {
+                self.map(|a| $op::$method(*a, value))
+            }
+        }
+
+        impl<T: Numeric> $op<T> for Column<T> {
+            type Output = Column<T>;
+
+            fn $method(mut self, value: T) -> Self::Output {
+                for a in self.as_mut_slice() {
+                    *a = $op::$method(*a, value);
+                }
+                self
+            }
+        }
+    };
+}
+
+impl_column_op!(Add, add);
+impl_column_op!(Sub, sub);
+impl_column_op!(Mul, mul);
+impl_column_op!(Div, div);
+
+/// Calculates gains and losses for consecutive elements of a slice.
+/// The first element has no predecessor, so its change is zero.
+pub(crate) fn gains_losses<T: Numeric>(
+    values: &[T],
+    max_history: Option<usize>,
+) -> (Column<T>, Column<T>) {
+    if values.is_empty() {
+        return (Column::new(), Column::new());
+    }
+
+    let len = values.len();
+    let start = match max_history {
+        Some(max) => len.saturating_sub(max),
+        None => 0,
+    };
+
+    let capacity = len - start;
+    let mut gains = Column::with_capacity(capacity);
+    let mut losses = Column::with_capacity(capacity);
+
+    for i in start..len {
+        let change = values[i] - values[i.saturating_sub(1)];
+        if change.is_positive() {
+            gains.push(change);
+            losses.push(T::ZERO);
+        } else {
+            gains.push(T::ZERO);
+            losses.push(change.abs());
+        }
+    }
+    (gains, losses)
+}
+
+impl<T> Default for Column<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Column<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T> Index<usize> for Column<T> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.raw[index]
+        &self.as_slice()[index]
     }
 }
 
-impl<T: Numeric> Index<Range<usize>> for Column<T> {
+impl<T> Index<Range<usize>> for Column<T> {
     type Output = [T];
 
     fn index(&self, range: Range<usize>) -> &Self::Output {
-        &self.raw[range]
+        &self.as_slice()[range]
     }
 }
 
-impl<T: Numeric> Index<RangeFrom<usize>> for Column<T> {
+impl<T> Index<RangeFrom<usize>> for Column<T> {
     type Output = [T];
 
     fn index(&self, range: RangeFrom<usize>) -> &Self::Output {
-        &self.raw[range]
+        &self.as_slice()[range]
     }
 }
 
-impl<T: Numeric> IndexMut<usize> for Column<T> {
+impl<T> IndexMut<usize> for Column<T> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.raw[index]
+        &mut self.as_mut_slice()[index]
     }
 }
 
-impl<T: Numeric> Extend<T> for Column<T> {
+impl<T> Extend<T> for Column<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        self.raw.extend(iter);
+        match self.max_len {
+            Some(_) => iter.into_iter().for_each(|value| self.push(value)),
+            None => self.raw.extend(iter),
+        }
     }
 }
 
-impl<T: Numeric + Display> Display for Column<T> {
+impl<T: Display> Display for Column<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[")?;
-        for (i, value) in self.raw.iter().enumerate() {
+        for (i, value) in self.iter().enumerate() {
             if i > 0 {
                 write!(f, ", ")?;
             }
@@ -193,61 +453,66 @@ impl<T: Numeric + Display> Display for Column<T> {
     }
 }
 
-impl<T: Numeric> From<Vec<T>> for Column<T> {
+impl<T> From<Vec<T>> for Column<T> {
     fn from(raw: Vec<T>) -> Self {
-        Self { raw }
+        Self {
+            raw,
+            head: 0,
+            max_len: None,
+        }
     }
 }
 
-impl<T: Numeric> From<Column<T>> for Vec<T> {
-    fn from(column: Column<T>) -> Self {
+impl<T> From<Column<T>> for Vec<T> {
+    fn from(mut column: Column<T>) -> Self {
+        column.compact();
         column.raw
     }
 }
 
-impl<T: Numeric> AsRef<[T]> for Column<T> {
+impl<T> AsRef<[T]> for Column<T> {
     fn as_ref(&self) -> &[T] {
-        &self.raw
+        self.as_slice()
     }
 }
 
-impl<T: Numeric> AsMut<[T]> for Column<T> {
+impl<T> AsMut<[T]> for Column<T> {
     fn as_mut(&mut self) -> &mut [T] {
-        &mut self.raw
+        self.as_mut_slice()
     }
 }
 
-impl<T: Numeric> IntoIterator for Column<T> {
+impl<T> IntoIterator for Column<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.raw.into_iter()
+        Vec::from(self).into_iter()
     }
 }
 
-impl<'a, T: Numeric + 'a> IntoIterator for &'a Column<T> {
+impl<'a, T: 'a> IntoIterator for &'a Column<T> {
     type Item = &'a T;
     type IntoIter = std::slice::Iter<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.raw.iter()
+        self.iter()
     }
 }
 
-impl<'a, T: Numeric + 'a> IntoIterator for &'a mut Column<T> {
+impl<'a, T: 'a> IntoIterator for &'a mut Column<T> {
     type Item = &'a mut T;
     type IntoIter = std::slice::IterMut<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.raw.iter_mut()
+        self.as_mut_slice().iter_mut()
     }
 }
 
-impl<T: Numeric> FromIterator<T> for Column<T> {
+impl<T> FromIterator<T> for Column<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let raw: Vec<T> = iter.into_iter().collect();
-        Self { raw }
+        raw.into()
     }
 }
 
@@ -269,4 +534,84 @@ mod tests {
         column.trim(3);
         assert_eq!(column.len(), 3);
     }
+
+    #[test]
+    fn test_bounded_column() {
+        let mut column = Column::with_max_len(3);
+        assert_eq!(column.raw.capacity(), 0);
+        for i in 0..10 {
+            column.push(i as f64);
+            assert!(column.len() <= 3);
+            assert!(column.raw.len() <= 6);
+            assert!(column.raw.capacity() <= 6);
+        }
+
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.as_slice(), &[7.0, 8.0, 9.0]);
+        assert_eq!(column[0], 7.0);
+        assert_eq!(column.last(), Some(&9.0));
+        assert_eq!(column, Column::from(vec![7.0, 8.0, 9.0]));
+        assert_eq!(Vec::from(column), vec![7.0, 8.0, 9.0]);
+
+        // Storage grows with use, up to twice the bound
+        let mut column = Column::with_max_len(1_000);
+        for i in 0..10 {
+            column.push(i);
+        }
+        assert!(column.raw.capacity() < 100);
+        for i in 0..10_000 {
+            column.push(i);
+        }
+        assert!(column.raw.capacity() <= 2_000);
+
+        // A huge bound does not allocate up front
+        let column = Column::<f64>::with_max_len(usize::MAX / 2 + 1);
+        assert_eq!(column.raw.capacity(), 0);
+    }
+
+    #[test]
+    fn test_column_arithmetic() {
+        let atr = Column::from(vec![1.0, 2.0, 3.0]);
+        let close = Column::from(vec![100.0, 50.0, 25.0]);
+
+        let natr = (&atr / &close).unwrap() * 100.0;
+        assert_eq!(natr.as_slice(), &[1.0, 4.0, 12.0]);
+        assert_eq!((&atr + 1.0).as_slice(), &[2.0, 3.0, 4.0]);
+        assert_eq!((atr.clone() - &atr).unwrap().as_slice(), &[0.0, 0.0, 0.0]);
+        assert_eq!((&atr * &atr).unwrap().as_slice(), &[1.0, 4.0, 9.0]);
+
+        assert_eq!(natr.ge(4.0).as_slice(), &[false, true, true]);
+        assert_eq!(natr.lt(4.0).as_slice(), &[true, false, false]);
+        assert_eq!(
+            atr.gt_column(&Column::from(vec![0.0, 2.0, 4.0]))
+                .unwrap()
+                .as_slice(),
+            &[true, false, false]
+        );
+
+        // Mismatched lengths are rejected instead of truncated
+        let short = Column::from(vec![1.0, 2.0]);
+        assert!(matches!(&atr + &short, Err(Error::LengthMismatch(3, 2))));
+        assert!(matches!(
+            atr.clone() * &short,
+            Err(Error::LengthMismatch(3, 2))
+        ));
+        assert!(atr.le_column(&short).is_err());
+        assert!(atr.zip_with(&short, |a, b| a + b).is_err());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_ewm_mean_decimal() {
+        use rust_decimal::{Decimal, prelude::ToPrimitive};
+
+        let values: Vec<f64> = (0..50).map(|i| ((i * 7) % 11) as f64 * 1.25).collect();
+        let expected = Column::from(values.clone()).into_ewm_mean(1.0 / 14.0);
+        let decimals: Column<Decimal> = values.iter().map(|v| Decimal::from_f64(*v)).collect();
+        let ewm = decimals.into_ewm_mean(Decimal::ONE / Decimal::from(14));
+
+        for (value, expected) in ewm.iter().zip(expected.iter()) {
+            assert!((value.to_f64().unwrap() - expected).abs() < 1e-9);
+        }
+    }
 }
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use super::Timeframe;
+
 /// Errors that can occur during technical analysis calculations.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -9,10 +11,34 @@ pub enum Error {
     #[error("Invalid timestamp: {0}")]
     InvalidTimestamp(u64),
 
+    /// Candles are missing between two timestamps and the gap policy rejects gaps.
+    #[error("Missing candles between {0} and {1}")]
+    MissingCandles(u64, u64),
+
+    /// Timeframe is empty or incompatible with the source timeframe.
+    #[error("Invalid timeframe: {0}")]
+    InvalidTimeframe(Timeframe),
+
+    /// Timeframe string could not be parsed.
+    #[error("Invalid timeframe string: {0:?}")]
+    ParseTimeframe(String),
+
     /// Insufficient data points for the requested calculation.
     #[error("Not enough data")]
     NotEnoughData,
 
+    /// Columns combined element-wise have different lengths.
+    #[error("Length mismatch: {0} != {1}")]
+    LengthMismatch(usize, usize),
+
+    /// An input value is not finite, or would complete more bars than allowed.
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// Indicator configuration is out of range, e.g. a zero period or an alpha outside (0, 1].
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+
     /// The time series contains no data.
     #[error("Empty time series: no data available")]
     EmptyTimeSeries,
@@ -0,0 +1,242 @@
+//! Exponentially weighted moving averages.
+
+use super::{Column, MaskedColumn, Numeric};
+
+/// How an exponentially weighted mean weights and seeds its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EwmMode {
+    /// `y[0] = x[0]`, `y[t] = alpha * x[t] + (1 - alpha) * y[t-1]`.
+    ///
+    /// Equivalent to pandas `ewm(adjust=False)` and to [`Column::into_ewm_mean`].
+    #[default]
+    Recursive,
+    /// `y[t] = sum((1 - alpha)^i * x[t-i]) / sum((1 - alpha)^i)` over all inputs so far.
+    ///
+    /// Equivalent to pandas `ewm(adjust=True)`, the pandas default used by pandas-ta.
+    Adjusted,
+    /// The first value is the simple mean of the first `period` inputs, then the
+    /// recursive formula applies. This is how TA-Lib and Wilder seed their averages;
+    /// the first `period - 1` values are masked.
+    SmaSeeded(usize),
+}
+
+/// Streaming exponentially weighted mean.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::core::{Ewm, EwmMode};
+///
+/// let mut ewm = Ewm::new(0.5, EwmMode::SmaSeeded(2), 0);
+/// assert_eq!(ewm.push(1.0), None);
+/// assert_eq!(ewm.push(3.0), Some(2.0));
+/// assert_eq!(ewm.push(4.0), Some(3.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ewm<T> {
+    alpha: T,
+    mode: EwmMode,
+    min_periods: usize,
+    count: usize,
+    mean: T,
+    // Running weighted sum and sum of weights for `Adjusted`, or the running sum
+    // of the seed window for `SmaSeeded`
+    sum: T,
+    weight: T,
+}
+
+impl<T: Numeric> Ewm<T> {
+    /// Creates an empty exponentially weighted mean.
+    ///
+    /// Values are masked until at least `min_periods` inputs have been seen,
+    /// in addition to the seed window of [`EwmMode::SmaSeeded`].
+    pub fn new(alpha: T, mode: EwmMode, min_periods: usize) -> Self {
+        debug_assert!(
+            alpha > T::ZERO && alpha <= T::ONE,
+            "Alpha must be between 0 and 1"
+        );
+
+        Self {
+            alpha,
+            mode,
+            min_periods,
+            count: 0,
+            mean: T::ZERO,
+            sum: T::ZERO,
+            weight: T::ZERO,
+        }
+    }
+
+    /// Returns the weighting mode.
+    pub fn mode(&self) -> EwmMode {
+        self.mode
+    }
+
+    /// Pushes a value and returns the new mean, or None while it is masked.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.mean = self.next_mean(value);
+        match self.mode {
+            EwmMode::Recursive => {}
+            EwmMode::Adjusted => {
+                self.sum = value + (T::ONE - self.alpha) * self.sum;
+                self.weight = T::ONE + (T::ONE - self.alpha) * self.weight;
+            }
+            EwmMode::SmaSeeded(period) => {
+                if self.count < period {
+                    self.sum += value;
+                }
+            }
+        }
+        self.count += 1;
+        self.value()
+    }
+
+    /// Returns the mean after pushing `value` without modifying the state,
+    /// or None if it would be masked.
+    pub fn peek(&self, value: T) -> Option<T> {
+        if !self.is_ready_after(self.count + 1) {
+            return None;
+        }
+        Some(self.next_mean(value))
+    }
+
+    /// Returns the current mean, or None while it is masked.
+    pub fn value(&self) -> Option<T> {
+        self.is_ready().then_some(self.mean)
+    }
+
+    /// Returns the current mean even while it is masked, or zero before any input.
+    pub(crate) fn mean(&self) -> T {
+        self.mean
+    }
+
+    /// Returns true once the mean is no longer masked.
+    pub fn is_ready(&self) -> bool {
+        self.is_ready_after(self.count)
+    }
+
+    /// Returns the number of values pushed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Clears all values, keeping the parameters.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.alpha, self.mode, self.min_periods);
+    }
+
+    fn is_ready_after(&self, count: usize) -> bool {
+        let seed = match self.mode {
+            EwmMode::SmaSeeded(period) => period,
+            EwmMode::Recursive | EwmMode::Adjusted => 1,
+        };
+        count >= seed.max(self.min_periods).max(1)
+    }
+
+    /// Calculates the mean after applying the given value.
+    pub(crate) fn next_mean(&self, value: T) -> T {
+        let alpha = self.alpha;
+        match self.mode {
+            EwmMode::Recursive if self.count == 0 => value,
+            EwmMode::Recursive => alpha * value + (T::ONE - alpha) * self.mean,
+            EwmMode::Adjusted => {
+                let sum = value + (T::ONE - alpha) * self.sum;
+                let weight = T::ONE + (T::ONE - alpha) * self.weight;
+                sum / weight
+            }
+            // Before the seed window is complete the mean covers the values seen so far
+            EwmMode::SmaSeeded(period) if self.count < period => {
+                (self.sum + value) / T::from_usize(self.count + 1)
+            }
+            EwmMode::SmaSeeded(_) => alpha * value + (T::ONE - alpha) * self.mean,
+        }
+    }
+}
+
+impl<T: Numeric> Column<T> {
+    /// Calculates the exponentially weighted mean of the column.
+    ///
+    /// Values are masked until at least `min_periods` inputs have been seen and,
+    /// for [`EwmMode::SmaSeeded`], until the seed window is complete.
+    pub fn ewm_mean(&self, alpha: T, mode: EwmMode, min_periods: usize) -> MaskedColumn<T> {
+        let mut ewm = Ewm::new(alpha, mode, min_periods);
+        let values: Column<T> = self
+            .iter()
+            .map(|value| {
+                ewm.push(*value);
+                ewm.mean
+            })
+            .collect();
+        let mask = (1..=values.len())
+            .map(|count| ewm.is_ready_after(count))
+            .collect();
+        MaskedColumn::new(values, mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{CLOSES, assert_close};
+
+    #[test]
+    fn test_recursive_matches_into_ewm_mean() {
+        let values = Column::from(CLOSES[..10].to_vec());
+        let ewm = values.ewm_mean(0.25, EwmMode::Recursive, 0);
+        assert_eq!(ewm.values(), &values.clone().into_ewm_mean(0.25));
+        assert_eq!(ewm.valid_count(), values.len());
+    }
+
+    #[test]
+    fn test_adjusted() {
+        // pandas: Series(values).ewm(alpha=0.25, adjust=True, min_periods=3).mean()
+        let expected = [
+            None,
+            None,
+            Some(48.56108108108108),
+            Some(48.58628571428571),
+            Some(48.636670934699104),
+            Some(48.75629343629344),
+            Some(48.84680143692329),
+            Some(48.97826197541331),
+            Some(49.23010612015953),
+            Some(49.468504649190976),
+        ];
+        let ewm = Column::from(CLOSES[..10].to_vec()).ewm_mean(0.25, EwmMode::Adjusted, 3);
+        assert_close(&ewm, &expected);
+        assert_eq!(ewm.values()[0], 48.16);
+    }
+
+    #[test]
+    fn test_sma_seeded() {
+        // TA-Lib style: SMA of the first 4 values, then the recursive formula
+        let expected = [
+            None,
+            None,
+            None,
+            Some(48.537499999999994),
+            Some(48.588125),
+            Some(48.69859375),
+            Some(48.7914453125),
+            Some(48.923583984375),
+            Some(49.17018798828125),
+            Some(49.41014099121094),
+        ];
+        let values = Column::from(CLOSES[..10].to_vec());
+        assert_close(&values.ewm_mean(0.25, EwmMode::SmaSeeded(4), 0), &expected);
+
+        // min_periods beyond the seed window masks further values
+        let ewm = values.ewm_mean(0.25, EwmMode::SmaSeeded(4), 6);
+        assert_eq!(ewm.first_valid(), Some(5));
+
+        // Streaming matches batch
+        let mut stream = Ewm::new(0.25, EwmMode::SmaSeeded(4), 0);
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(stream.peek(*value), stream.clone().push(*value));
+            let value = stream.push(*value);
+            assert_eq!(value.is_some(), expected[i].is_some());
+            if let (Some(value), Some(expected)) = (value, expected[i]) {
+                assert!((value - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
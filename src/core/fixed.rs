@@ -0,0 +1,580 @@
+//! Fixed-point decimal numbers backed by integers.
+
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use super::Numeric;
+
+/// Fixed-point decimal number stored as an `i64` count of `10^-SCALE` units.
+///
+/// Exchange prices and quantities quoted as integer ticks can be stored without
+/// conversion, and every operation is integer arithmetic, so indicators produce
+/// bit-identical results on every machine. Products and quotients are computed
+/// with 128-bit intermediates and rounded half away from zero to `SCALE` digits.
+/// `ln`, `exp` and `powf` are evaluated with 18 decimal digits of working
+/// precision before rounding, so only their last digit may be off.
+///
+/// Operators panic on overflow and division by zero, like the checked integer
+/// operations in debug builds; use the `checked_*` methods to handle them.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::core::Fixed;
+///
+/// type Price = Fixed<4>;
+///
+/// let price = Price::from_raw(1_234_500); // 123.45 with 4 decimals
+/// assert_eq!(price * Price::from_int(2), Price::from_raw(2_469_000));
+/// assert_eq!(price.to_string(), "123.4500");
+/// assert_eq!(Price::from_raw(i64::MAX).checked_add(Price::from_raw(1)), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<const SCALE: u32>(i64);
+
+impl<const SCALE: u32> Fixed<SCALE> {
+    /// Number of raw units in one whole unit, i.e. `10^SCALE`.
+    pub const FACTOR: i64 = 10i64.pow(SCALE);
+
+    /// Smallest representable value.
+    pub const MIN: Self = Fixed(i64::MIN);
+
+    /// Largest representable value.
+    pub const MAX: Self = Fixed(i64::MAX);
+
+    /// Creates a value from a raw count of `10^-SCALE` units.
+    pub const fn from_raw(raw: i64) -> Self {
+        Fixed(raw)
+    }
+
+    /// Returns the raw count of `10^-SCALE` units.
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    /// Creates a value from a whole number.
+    ///
+    /// # Panics
+    /// Panics if the value is out of range.
+    pub fn from_int(value: i64) -> Self {
+        Self::checked_from_int(value).expect("fixed-point overflow")
+    }
+
+    /// Creates a value from a whole number, or None if it is out of range.
+    pub fn checked_from_int(value: i64) -> Option<Self> {
+        value.checked_mul(Self::FACTOR).map(Fixed)
+    }
+
+    /// Creates a value from the nearest `10^-SCALE` multiple of a float, or None
+    /// if it is out of range or not finite.
+    pub fn checked_from_f64(value: f64) -> Option<Self> {
+        let raw = (value * Self::FACTOR as f64).round();
+        // i64::MAX as f64 rounds up to 2^63, which is out of range
+        if raw.is_finite() && raw >= i64::MIN as f64 && raw < i64::MAX as f64 {
+            Some(Fixed(raw as i64))
+        } else {
+            None
+        }
+    }
+
+    /// Converts to the nearest `f64`, e.g. for display or interop.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::FACTOR as f64
+    }
+
+    /// Checked addition. Returns None on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Fixed)
+    }
+
+    /// Checked subtraction. Returns None on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Fixed)
+    }
+
+    /// Checked multiplication. Returns None on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let product = self.0 as i128 * other.0 as i128;
+        Self::from_i128(round_div(product, Self::FACTOR as i128))
+    }
+
+    /// Checked division. Returns None on overflow or division by zero.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.0 == 0 {
+            return None;
+        }
+        let numerator = self.0 as i128 * Self::FACTOR as i128;
+        Self::from_i128(round_div(numerator, other.0 as i128))
+    }
+
+    /// Checked remainder. Returns None on division by zero or overflow.
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        self.0.checked_rem(other.0).map(Fixed)
+    }
+
+    /// Checked absolute value. Returns None for [`Fixed::MIN`].
+    pub fn checked_abs(self) -> Option<Self> {
+        self.0.checked_abs().map(Fixed)
+    }
+
+    /// Checked square root, rounded down. Returns None for negative values.
+    pub fn checked_sqrt(self) -> Option<Self> {
+        if self.0 < 0 {
+            return None;
+        }
+        // sqrt(raw / F) * F == sqrt(raw * F)
+        Self::from_i128((self.0 as i128 * Self::FACTOR as i128).isqrt())
+    }
+
+    /// Checked integer power, computed by repeated squaring with the same rounding
+    /// as multiplication. Returns None on overflow or division by zero.
+    pub fn checked_powi(self, n: i32) -> Option<Self> {
+        let mut result = Self::ONE;
+        let mut base = self;
+        let mut exp = n.unsigned_abs();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        if n < 0 {
+            Self::ONE.checked_div(result)
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Checked natural logarithm. Returns None for non-positive values or on overflow.
+    pub fn checked_ln(self) -> Option<Self> {
+        if self.0 <= 0 {
+            return None;
+        }
+        Self::from_wide(ln_wide(self.to_wide()))
+    }
+
+    /// Checked exponential. Returns None on overflow.
+    pub fn checked_exp(self) -> Option<Self> {
+        Self::from_wide(exp_wide(self.to_wide())?)
+    }
+
+    /// Checked power, computed as `exp(n * ln(self))` or with [`Fixed::checked_powi`]
+    /// for whole exponents. Returns None for a negative base with a fractional
+    /// exponent, zero to a negative power, or on overflow.
+    pub fn checked_powf(self, n: Self) -> Option<Self> {
+        if n.0 % Self::FACTOR == 0
+            && let Ok(n) = i32::try_from(n.0 / Self::FACTOR)
+        {
+            return self.checked_powi(n);
+        }
+        if self.0 <= 0 {
+            return (self.0 == 0 && n.0 > 0).then_some(Self::ZERO);
+        }
+
+        let ln = ln_wide(self.to_wide());
+        let exponent = match ln.checked_mul(n.to_wide()) {
+            Some(product) => round_div(product, WIDE),
+            // Far beyond the range of any scale
+            None if ln.signum() == n.0.signum() as i128 => return None,
+            None => return Some(Self::ZERO),
+        };
+        Self::from_wide(exp_wide(exponent)?)
+    }
+
+    fn from_i128(value: i128) -> Option<Self> {
+        i64::try_from(value).ok().map(Fixed)
+    }
+
+    /// Converts to `10^-18` units, the working precision of transcendental functions.
+    fn to_wide(self) -> i128 {
+        self.0 as i128 * 10i128.pow(WIDE_DIGITS - SCALE)
+    }
+
+    fn from_wide(value: i128) -> Option<Self> {
+        Self::from_i128(round_div(value, 10i128.pow(WIDE_DIGITS - SCALE)))
+    }
+}
+
+/// Decimal digits used by transcendental functions, the most any scale can hold.
+const WIDE_DIGITS: u32 = 18;
+const WIDE: i128 = 10i128.pow(WIDE_DIGITS);
+/// `ln(2)` in `10^-18` units, and the following 18 digits to keep multiples exact.
+const LN_2: i128 = 693_147_180_559_945_309;
+const LN_2_LOW: i128 = 417_232_121_458_176_568;
+
+/// Returns `k * ln(2)` in `10^-18` units.
+fn ln_2_times(k: i128) -> i128 {
+    k * LN_2 + round_div(k * LN_2_LOW, WIDE)
+}
+
+/// Natural logarithm of a positive `10^-18` fixed-point value.
+fn ln_wide(x: i128) -> i128 {
+    // x = 2^k * m with m in [1, 2)
+    let mut k = x.ilog2() as i128 - WIDE.ilog2() as i128;
+    let mut m = mul_pow2(x, -k);
+    while m >= 2 * WIDE {
+        k += 1;
+        m = mul_pow2(x, -k);
+    }
+    while m < WIDE {
+        k -= 1;
+        m = mul_pow2(x, -k);
+    }
+
+    // ln(m) = 2 * atanh(s) = 2 * (s + s^3 / 3 + s^5 / 5 + ...) with s < 1/3
+    let s = round_div((m - WIDE) * WIDE, m + WIDE);
+    let s2 = round_div(s * s, WIDE);
+    let (mut power, mut sum, mut n) = (s, 0, 1);
+    while power != 0 {
+        sum += round_div(power, n);
+        power = round_div(power * s2, WIDE);
+        n += 2;
+    }
+    2 * sum + ln_2_times(k)
+}
+
+/// Exponential of a `10^-18` fixed-point value, or None if it overflows `i128`.
+fn exp_wide(x: i128) -> Option<i128> {
+    // exp(x) = 2^k * exp(r) with |r| <= ln(2) / 2
+    let k = round_div(x, LN_2);
+    match k {
+        // exp(r) >= 2^59 units, so any larger power of two overflows
+        68.. => return None,
+        ..-126 => return Some(0),
+        _ => {}
+    }
+
+    let r = x - ln_2_times(k);
+    let (mut term, mut sum, mut n) = (WIDE, WIDE, 1);
+    while term != 0 {
+        term = round_div(term * r, WIDE * n);
+        sum += term;
+        n += 1;
+    }
+    if k >= 0 {
+        sum.checked_mul(1 << k)
+    } else {
+        Some(mul_pow2(sum, k))
+    }
+}
+
+/// Multiplies by `2^k`, rounding half away from zero when `k` is negative.
+fn mul_pow2(value: i128, k: i128) -> i128 {
+    match k {
+        0.. => value << k,
+        -126..0 => round_div(value, 1 << -k),
+        _ => 0,
+    }
+}
+
+/// Divides and rounds half away from zero.
+fn round_div(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.unsigned_abs() * 2 >= denominator.unsigned_abs() {
+        quotient + (numerator.signum() * denominator.signum())
+    } else {
+        quotient
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($op:ident, $method:ident, $assign_op:ident, $assign_method:ident, $checked:ident, $msg:literal) => {
+        impl<const SCALE: u32> $op for Fixed<SCALE> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                self.$checked(other).expect($msg)
+            }
+        }
+
+        impl<const SCALE: u32> $assign_op for Fixed<SCALE> {
+            fn $assign_method(&mut self, other: Self) {
+                *self = $op::$method(*self, other);
+            }
+        }
+    };
+}
+
+impl_checked_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    checked_add,
+    "fixed-point overflow"
+);
+impl_checked_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    checked_sub,
+    "fixed-point overflow"
+);
+impl_checked_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    checked_mul,
+    "fixed-point overflow"
+);
+impl_checked_op!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    checked_div,
+    "fixed-point division by zero or overflow"
+);
+impl_checked_op!(
+    Rem,
+    rem,
+    RemAssign,
+    rem_assign,
+    checked_rem,
+    "fixed-point division by zero or overflow"
+);
+
+impl<const SCALE: u32> Neg for Fixed<SCALE> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fixed(self.0.checked_neg().expect("fixed-point overflow"))
+    }
+}
+
+impl<const SCALE: u32> Sum for Fixed<SCALE> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, value| acc + value)
+    }
+}
+
+impl<const SCALE: u32> Display for Fixed<SCALE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        let factor = Self::FACTOR as u64;
+        if SCALE == 0 {
+            write!(f, "{sign}{abs}")
+        } else {
+            write!(
+                f,
+                "{sign}{}.{:0width$}",
+                abs / factor,
+                abs % factor,
+                width = SCALE as usize
+            )
+        }
+    }
+}
+
+impl<const SCALE: u32> Numeric for Fixed<SCALE> {
+    const ZERO: Self = Fixed(0);
+    const ONE: Self = Fixed(Self::FACTOR);
+
+    fn two() -> Self {
+        Self::from_int(2)
+    }
+    fn fifty() -> Self {
+        Self::from_int(50)
+    }
+    fn hundred() -> Self {
+        Self::from_int(100)
+    }
+    fn abs(self) -> Self {
+        self.checked_abs().expect("fixed-point overflow")
+    }
+    fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+    fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+    fn signum(self) -> Self {
+        Self::from_int(self.0.signum())
+    }
+    fn sqrt(self) -> Self {
+        self.checked_sqrt()
+            .expect("square root of negative fixed-point value")
+    }
+    fn ln(self) -> Self {
+        if self.0 <= 0 {
+            panic!("logarithm of non-positive fixed-point value");
+        }
+        self.checked_ln().expect("fixed-point overflow")
+    }
+    fn exp(self) -> Self {
+        self.checked_exp().expect("fixed-point overflow")
+    }
+    fn powi(self, n: i32) -> Self {
+        self.checked_powi(n).expect("fixed-point overflow")
+    }
+    fn powf(self, n: Self) -> Self {
+        if self.0 < 0 && n.0 % Self::FACTOR != 0 {
+            panic!("fractional power of negative fixed-point value");
+        }
+        self.checked_powf(n).expect("fixed-point overflow")
+    }
+    fn is_finite(self) -> bool {
+        true
+    }
+    fn from_usize(n: usize) -> Self {
+        i64::try_from(n)
+            .ok()
+            .and_then(Self::checked_from_int)
+            .expect("fixed-point overflow")
+    }
+    fn from_f64(n: f64) -> Self {
+        Self::checked_from_f64(n).expect("fixed-point overflow")
+    }
+    fn to_f64(self) -> f64 {
+        self.to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        indicators::{Config, rsi_series},
+        test_utils::candles_from_closes,
+    };
+
+    type Price = Fixed<4>;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Price::from_raw(12_345); // 1.2345
+        let b = Price::from_int(2);
+
+        assert_eq!(a + b, Price::from_raw(32_345));
+        assert_eq!(a - b, Price::from_raw(-7_655));
+        assert_eq!(a * b, Price::from_raw(24_690));
+        assert_eq!(a / b, Price::from_raw(6_173)); // 0.61725 rounds away from zero
+        assert_eq!(-a / b, Price::from_raw(-6_173));
+        assert_eq!(Price::ONE / Price::from_int(3), Price::from_raw(3_333));
+        assert_eq!(a % Price::ONE, Price::from_raw(2_345));
+        assert_eq!(
+            vec![a, a, b].into_iter().sum::<Price>(),
+            Price::from_raw(44_690)
+        );
+
+        assert_eq!(a.to_string(), "1.2345");
+        assert_eq!((-a).to_string(), "-1.2345");
+        assert_eq!(Fixed::<0>::from_int(-7).to_string(), "-7");
+        assert_eq!(a.to_f64(), 1.2345);
+    }
+
+    #[test]
+    fn test_checked_overflow() {
+        assert_eq!(Price::MAX.checked_add(Price::ONE), None);
+        assert_eq!(Price::MIN.checked_sub(Price::ONE), None);
+        assert_eq!(Price::MAX.checked_mul(Price::two()), None);
+        assert_eq!(Price::ONE.checked_div(Price::ZERO), None);
+        assert_eq!(Price::MIN.checked_abs(), None);
+        assert_eq!(Price::checked_from_int(i64::MAX), None);
+        assert_eq!(Price::from_raw(-1).checked_sqrt(), None);
+        assert_eq!(Price::from_int(2).sqrt(), Price::from_raw(14_142));
+        assert_eq!(Price::checked_from_f64(f64::NAN), None);
+        assert_eq!(Price::checked_from_f64(1e18), None);
+        assert_eq!(Price::from_f64(-1.23456), Price::from_raw(-12_346));
+        assert_eq!(Price::from_int(3).powi(4), Price::from_int(81));
+        assert_eq!(Price::from_int(3).powi(-1), Price::from_raw(3_333));
+        assert_eq!(Price::from_int(10).checked_powi(15), None);
+        assert_eq!(Price::ZERO.checked_powi(-1), None);
+        assert_eq!(Price::from_int(-5).signum(), Price::from_int(-1));
+        assert_eq!(Price::ONE.exp(), Price::from_raw(27_183));
+        assert_eq!(Price::ONE.ln(), Price::ZERO);
+        assert_eq!(Fixed::<0>::from_int(44).checked_exp(), None);
+        assert_eq!(Fixed::<0>::MIN.checked_exp(), Some(Fixed::ZERO));
+        assert_eq!(Fixed::<18>::from_raw(1).checked_ln(), None);
+        assert_eq!(Price::ZERO.checked_ln(), None);
+        assert_eq!(
+            Price::from_int(-2).checked_powf(Price::from_raw(5_000)),
+            None
+        );
+        assert_eq!(Price::ZERO.checked_powf(Price::from_raw(-5_000)), None);
+        assert_eq!(Price::MAX.checked_powf(Price::MAX), None);
+
+        // 128-bit intermediates avoid spurious overflow
+        let large = Price::from_int(1_000_000_000);
+        assert_eq!(large * Price::from_raw(5_000), Price::from_int(500_000_000));
+    }
+
+    #[test]
+    fn test_transcendental() {
+        type Precise = Fixed<8>;
+
+        // Expected values from Python's `decimal` module with 60 digits, rounded half up
+        let value = |raw| Precise::from_raw(raw);
+        assert_eq!(Precise::ONE.exp(), value(271_828_183));
+        assert_eq!(value(-350_000_000).exp(), value(3_019_738));
+        assert_eq!(Precise::two().ln(), value(69_314_718));
+        assert_eq!(value(12_345_600_000).ln(), value(481_588_482));
+        assert_eq!(Precise::two().powf(value(50_000_000)), value(141_421_356));
+        assert_eq!(
+            value(150_000_000).powf(value(250_000_000)),
+            value(275_567_596)
+        );
+        assert_eq!(
+            value(25_000_000).powf(value(-150_000_000)),
+            Precise::from_int(8)
+        );
+        assert_eq!(
+            Precise::from_int(-2).powf(Precise::from_int(3)),
+            Precise::from_int(-8)
+        );
+        assert_eq!(Precise::ZERO.powf(Precise::two()), Precise::ZERO);
+
+        // Results using all 19 digits are within a few units of the exact
+        // 4727839468229346561 and 2221740701383732181
+        let wide = Fixed::<0>::from_int(43).exp();
+        assert_eq!(wide, Fixed::from_raw(4_727_839_468_229_346_560));
+        let narrow = Fixed::<18>::MAX.ln();
+        assert_eq!(narrow, Fixed::from_raw(2_221_740_701_383_732_178));
+    }
+
+    #[test]
+    #[should_panic(expected = "fractional power of negative fixed-point value")]
+    fn test_negative_fractional_power_panics() {
+        let _ = Price::from_int(-2).powf(Price::from_raw(5_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "fixed-point overflow")]
+    fn test_overflow_panics() {
+        let _ = Price::MAX + Price::ONE;
+    }
+
+    #[test]
+    fn test_rsi_series_fixed() {
+        let prices = [
+            44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03,
+            45.61, 46.28, 46.28, 46.00, 46.03, 46.41, 46.22, 45.64, 46.21, 46.25,
+        ];
+        let fixed_prices: Vec<Fixed<8>> = prices
+            .iter()
+            .map(|&price| Fixed::from_raw((price * 100.0_f64).round() as i64 * 1_000_000))
+            .collect();
+        let candles = candles_from_closes(&prices);
+        let fixed_candles = candles_from_closes(&fixed_prices);
+
+        let config = Config::wilder(14, 100);
+        let fixed_config = Config::<Fixed<8>>::wilder(14, 100);
+        let expected = rsi_series(&candles, &config).unwrap();
+        let rsi_values = rsi_series(&fixed_candles, &fixed_config).unwrap();
+
+        for (value, expected) in rsi_values.values().iter().zip(expected.values().iter()) {
+            assert!((value.to_f64() - expected).abs() < 1e-4);
+        }
+        // Integer arithmetic pins the exact result on every platform
+        assert_eq!(rsi_values.last().unwrap().raw(), 6_169_050_800);
+    }
+}
@@ -0,0 +1,209 @@
+//! Columns with a validity mask for indicator outputs.
+
+use std::fmt::Display;
+
+use super::Column;
+
+/// Column of values paired with a validity mask.
+///
+/// Indicators return masked columns so that values computed before the
+/// indicator has converged, such as the first `period` bars of an RSI, are
+/// never mistaken for real readings. Masked positions still hold the raw
+/// computed value, available through [`MaskedColumn::values`], but every
+/// accessor that yields a value returns None for them.
+#[derive(Debug, Clone)]
+pub struct MaskedColumn<T> {
+    values: Column<T>,
+    mask: Column<bool>,
+}
+
+impl<T> MaskedColumn<T> {
+    /// Creates a masked column from values and a mask where `true` marks a valid value.
+    ///
+    /// # Panics
+    /// Panics if `values` and `mask` have different lengths.
+    pub fn new(values: Column<T>, mask: Column<bool>) -> Self {
+        if values.len() != mask.len() {
+            panic!("values and mask must have the same length");
+        }
+
+        Self { values, mask }
+    }
+
+    /// Creates a masked column whose first `warmup` values are invalid.
+    pub fn with_warmup(values: Column<T>, warmup: usize) -> Self {
+        let mask = (0..values.len()).map(|i| i >= warmup).collect();
+        Self { values, mask }
+    }
+
+    /// Returns the number of elements, valid or not.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if the column is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns true if the element at the specified index exists and is valid.
+    pub fn is_valid(&self, index: usize) -> bool {
+        self.mask.get(index).copied().unwrap_or(false)
+    }
+
+    /// Returns the number of valid elements.
+    pub fn valid_count(&self) -> usize {
+        self.mask.iter().filter(|valid| **valid).count()
+    }
+
+    /// Returns the index of the first valid element, or None if there is none.
+    pub fn first_valid(&self) -> Option<usize> {
+        self.mask.iter().position(|valid| *valid)
+    }
+
+    /// Returns the raw values, including those that are masked.
+    pub fn values(&self) -> &Column<T> {
+        &self.values
+    }
+
+    /// Returns the validity mask.
+    pub fn mask(&self) -> &Column<bool> {
+        &self.mask
+    }
+
+    /// Splits the column into its raw values and mask.
+    pub fn into_parts(self) -> (Column<T>, Column<bool>) {
+        (self.values, self.mask)
+    }
+
+    /// Applies a function to each valid element, keeping the mask.
+    pub fn map<U: Default, F>(&self, mut f: F) -> MaskedColumn<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let values = self
+            .values
+            .iter()
+            .zip(self.mask.iter())
+            .map(|(value, valid)| if *valid { f(value) } else { U::default() })
+            .collect();
+        MaskedColumn {
+            values,
+            mask: self.mask.clone(),
+        }
+    }
+}
+
+impl<T: Copy> MaskedColumn<T> {
+    /// Gets the element at the specified index, or None if it is masked or out of bounds.
+    pub fn get(&self, index: usize) -> Option<T> {
+        if self.is_valid(index) {
+            Some(self.values[index])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the last element, or None if it is masked or the column is empty.
+    pub fn last(&self) -> Option<T> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Returns an iterator over the elements, yielding None for masked ones.
+    pub fn iter(&self) -> impl Iterator<Item = Option<T>> + '_ {
+        self.values
+            .iter()
+            .zip(self.mask.iter())
+            .map(|(value, valid)| valid.then_some(*value))
+    }
+
+    /// Returns an iterator over the indices and values of valid elements.
+    pub fn valid(&self) -> impl Iterator<Item = (usize, T)> + '_ {
+        self.iter()
+            .enumerate()
+            .filter_map(|(i, value)| value.map(|value| (i, value)))
+    }
+
+    /// Converts the column into a column of options.
+    pub fn to_options(&self) -> Column<Option<T>> {
+        self.iter().collect()
+    }
+}
+
+impl<T> Default for MaskedColumn<T> {
+    fn default() -> Self {
+        Self {
+            values: Column::new(),
+            mask: Column::new(),
+        }
+    }
+}
+
+impl<T: Copy + PartialEq> PartialEq for MaskedColumn<T> {
+    /// Two masked columns are equal if they have the same mask and the same valid values.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Default> FromIterator<Option<T>> for MaskedColumn<T> {
+    fn from_iter<I: IntoIterator<Item = Option<T>>>(iter: I) -> Self {
+        let mut values = Column::new();
+        let mut mask = Column::new();
+        for value in iter {
+            mask.push(value.is_some());
+            values.push(value.unwrap_or_default());
+        }
+        Self { values, mask }
+    }
+}
+
+impl<T: Default> From<Column<Option<T>>> for MaskedColumn<T> {
+    fn from(column: Column<Option<T>>) -> Self {
+        column.into_iter().collect()
+    }
+}
+
+impl<T: Display> Display for MaskedColumn<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, (value, valid)) in self.values.iter().zip(self.mask.iter()).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            if *valid {
+                write!(f, "{}", value)?;
+            } else {
+                write!(f, "-")?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_column() {
+        let column = MaskedColumn::with_warmup(Column::from(vec![1.0, 2.0, 3.0, 4.0]), 2);
+
+        assert_eq!(column.len(), 4);
+        assert_eq!(column.valid_count(), 2);
+        assert_eq!(column.first_valid(), Some(2));
+        assert_eq!(column.get(1), None);
+        assert_eq!(column.get(2), Some(3.0));
+        assert_eq!(column.get(4), None);
+        assert_eq!(column.last(), Some(4.0));
+        assert_eq!(column.values()[0], 1.0);
+        assert_eq!(column.valid().collect::<Vec<_>>(), vec![(2, 3.0), (3, 4.0)]);
+        assert_eq!(column.to_string(), "[-, -, 3, 4]");
+
+        // Masked raw values do not take part in equality
+        let options: MaskedColumn<f64> =
+            vec![None, None, Some(3.0), Some(4.0)].into_iter().collect();
+        assert_eq!(column, options);
+        assert_eq!(column.map(|v| v * 2.0).last(), Some(8.0));
+    }
+}
@@ -1,11 +1,27 @@
 //! Core data structures and traits for technical analysis.
 
+mod aligned;
 mod candle;
 mod column;
 mod error;
+mod ewm;
+mod fixed;
+mod masked;
+mod resample;
+mod rolling;
+mod timeframe;
 mod traits;
+mod view;
 
+pub use aligned::*;
 pub use candle::*;
 pub use column::*;
 pub use error::*;
+pub use ewm::*;
+pub use fixed::*;
+pub use masked::*;
+pub use resample::*;
+pub use rolling::*;
+pub use timeframe::*;
 pub use traits::*;
+pub use view::*;
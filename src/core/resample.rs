@@ -0,0 +1,286 @@
+//! Aggregation of candles into higher timeframes.
+
+use super::{Candle, Error, Numeric, Timeframe};
+
+/// How a trailing bucket that is not yet complete is handled when resampling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialBucket {
+    /// Include the incomplete bucket as the last candle.
+    Keep,
+    /// Leave the incomplete bucket out of the result.
+    Drop,
+}
+
+/// Streaming aggregator merging candles of one timeframe into a higher timeframe.
+///
+/// Candles are merged by taking the first open, the highest high, the lowest low,
+/// the last close and the summed volume of each bucket. A bucket is emitted as
+/// soon as a candle covering its end arrives, or when a candle from a later
+/// bucket arrives. After a gap, a single candle can do both: close the previous
+/// bucket and complete the one it opens.
+#[derive(Debug, Clone)]
+pub struct Resampler<T> {
+    source: Timeframe,
+    target: Timeframe,
+    current: Option<(Candle<T>, u64)>,
+    /// Start of the last bucket handed out, which no later candle may reopen.
+    emitted: Option<u64>,
+}
+
+impl<T: Numeric> Resampler<T> {
+    /// Creates a resampler from `source` to `target` timeframe.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidTimeframe` if either timeframe is empty or the
+    /// boundaries of `target` do not line up with those of `source`.
+    pub fn new(source: impl Into<Timeframe>, target: impl Into<Timeframe>) -> Result<Self, Error> {
+        let source = source.into();
+        let target = target.into();
+        if !target.is_multiple_of(&source) {
+            return Err(Error::InvalidTimeframe(target));
+        }
+
+        Ok(Self {
+            source,
+            target,
+            current: None,
+            emitted: None,
+        })
+    }
+
+    /// Returns the target timeframe.
+    pub fn timeframe(&self) -> Timeframe {
+        self.target
+    }
+
+    /// Pushes a source candle starting at `ts`.
+    /// Returns the buckets completed by the candle and their start timestamps, oldest first.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidTimestamp` if the candle belongs to a bucket before the
+    /// current one, or to a bucket that has already been emitted or flushed, or if
+    /// it straddles the end of its bucket, as candles of whole hours do at the
+    /// midnight of a timezone with a half-hour offset.
+    pub fn push(
+        &mut self,
+        candle: Candle<T>,
+        ts: u64,
+    ) -> Result<impl Iterator<Item = (Candle<T>, u64)> + use<T>, Error> {
+        let start = self.target.bucket_start(ts);
+        if self.emitted.is_some_and(|emitted| start <= emitted)
+            || self.source.next_start(ts) > self.target.next_start(start)
+        {
+            return Err(Error::InvalidTimestamp(ts));
+        }
+        let mut completed = [None, None];
+
+        match self.current.take() {
+            None => {
+                self.current = Some((candle, start));
+            }
+            Some((mut bucket, bucket_start)) => match start.cmp(&bucket_start) {
+                // A candle from a later bucket closes the current one
+                std::cmp::Ordering::Greater => {
+                    self.current = Some((candle, start));
+                    completed[0] = Some((bucket, bucket_start));
+                }
+                std::cmp::Ordering::Equal => {
+                    merge(&mut bucket, &candle);
+                    self.current = Some((bucket, bucket_start));
+                }
+                std::cmp::Ordering::Less => {
+                    self.current = Some((bucket, bucket_start));
+                    return Err(Error::InvalidTimestamp(ts));
+                }
+            },
+        }
+
+        // A candle covering the end of its bucket completes it immediately, even if
+        // the candle has just opened that bucket
+        if self.source.next_start(ts) >= self.target.next_start(start) {
+            completed[1] = self.current.take();
+        }
+
+        if let Some((_, start)) = completed.iter().flatten().last() {
+            self.emitted = Some(*start);
+        }
+        Ok(completed.into_iter().flatten())
+    }
+
+    /// Returns the in-progress bucket and its start timestamp, if any.
+    pub fn partial(&self) -> Option<(Candle<T>, u64)> {
+        self.current
+    }
+
+    /// Takes the in-progress bucket, leaving the resampler empty.
+    /// Later candles belonging to the flushed bucket are rejected.
+    pub fn flush(&mut self) -> Option<(Candle<T>, u64)> {
+        let flushed = self.current.take();
+        if let Some((_, start)) = flushed {
+            self.emitted = Some(start);
+        }
+        flushed
+    }
+}
+
+/// Merges a later candle into an aggregated bucket.
+fn merge<T: Numeric>(bucket: &mut Candle<T>, candle: &Candle<T>) {
+    if candle.high > bucket.high {
+        bucket.high = candle.high;
+    }
+    if candle.low < bucket.low {
+        bucket.low = candle.low;
+    }
+    bucket.close = candle.close;
+    bucket.volume += candle.volume;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resampler_streaming() {
+        let mut resampler = Resampler::new(60, 120).unwrap();
+
+        let mut emitted = Vec::new();
+        for i in 0..7usize {
+            let price = (i + 1) as f64;
+            let candle = Candle {
+                open: price,
+                high: price + 1.0,
+                low: price - 1.0,
+                close: price + 0.5,
+                volume: 10.0,
+            };
+            emitted.extend(resampler.push(candle, (i as u64) * 60).unwrap());
+            // Buckets are emitted as soon as their last candle arrives
+            assert_eq!(emitted.len(), i.div_ceil(2));
+        }
+
+        let (partial, start) = resampler.partial().unwrap();
+        assert_eq!(start, 360);
+        assert_eq!(partial.open, 7.0);
+        assert!(resampler.push(partial, 0).is_err());
+        assert_eq!(resampler.partial().unwrap().1, 360);
+        assert!(resampler.flush().is_some());
+        assert!(resampler.partial().is_none());
+    }
+
+    #[test]
+    fn test_resampler_calendar() {
+        // Daily candles from 2024-01-29 to 2024-02-02 into months
+        let day = 86_400_000;
+        let start = 1_706_486_400_000;
+        let mut resampler = Resampler::new(Timeframe::days(1), Timeframe::months(1)).unwrap();
+
+        let mut emitted = Vec::new();
+        for i in 0..5u64 {
+            let price = i as f64;
+            let candle = Candle {
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: 1.0,
+            };
+            emitted.extend(resampler.push(candle, start + i * day).unwrap());
+        }
+
+        // January is emitted as soon as its last day arrives
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(emitted[0].0.volume, 3.0);
+        assert_eq!(emitted[0].1, 1_704_067_200_000);
+        assert_eq!(resampler.partial().unwrap().1, 1_706_745_600_000);
+
+        assert!(Resampler::<f64>::new(Timeframe::days(1), Timeframe::hours(36)).is_err());
+    }
+
+    #[test]
+    fn test_resampler_gap_before_complete_bucket() {
+        let mut resampler = Resampler::new(60, 180).unwrap();
+        let candle = |price: f64| Candle {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 1.0,
+        };
+
+        assert_eq!(resampler.push(candle(1.0), 0).unwrap().count(), 0);
+
+        // After a gap, the last candle of a bucket both closes the previous bucket
+        // and completes its own
+        let emitted: Vec<_> = resampler.push(candle(2.0), 300).unwrap().collect();
+        assert_eq!(emitted, vec![(candle(1.0), 0), (candle(2.0), 180)]);
+        assert!(resampler.partial().is_none());
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn test_resampler_zoned() {
+        use crate::core::CalendarUnit;
+        use chrono_tz::{America, Asia};
+
+        let hour = 3_600_000;
+        // 2024-03-09T00:00Z, the day before New York's switch to daylight saving time
+        let start = 1_709_942_400_000;
+        let candle = Candle {
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+        };
+
+        let daily = Timeframe::zoned(1, CalendarUnit::Day, America::New_York);
+        let mut resampler = Resampler::new(Timeframe::hours(1), daily).unwrap();
+        let mut emitted = Vec::new();
+        for i in 0..72 {
+            emitted.extend(resampler.push(candle, start + i * hour).unwrap());
+        }
+        let hours: Vec<_> = emitted.iter().map(|(bucket, _)| bucket.volume).collect();
+        assert_eq!(hours, vec![5.0, 24.0, 23.0]);
+        assert_eq!(emitted[2].1, start + 29 * hour);
+
+        // Hourly candles straddle midnight in UTC+05:30
+        let daily = Timeframe::zoned(1, CalendarUnit::Day, Asia::Kolkata);
+        let mut resampler = Resampler::new(Timeframe::hours(1), daily).unwrap();
+        assert!(resampler.push(candle, start).is_ok());
+        assert!(matches!(
+            resampler.push(candle, start + 18 * hour),
+            Err(Error::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_resampler_rejects_emitted_bucket() {
+        let mut resampler = Resampler::new(60, 120).unwrap();
+        let candle = |price: f64| Candle {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 1.0,
+        };
+
+        assert_eq!(resampler.push(candle(1.0), 0).unwrap().count(), 0);
+        assert_eq!(resampler.push(candle(2.0), 60).unwrap().count(), 1);
+
+        // Bucket 0 has been emitted, so neither of its candles may arrive again
+        assert!(matches!(
+            resampler.push(candle(3.0), 0),
+            Err(Error::InvalidTimestamp(0))
+        ));
+        assert!(matches!(
+            resampler.push(candle(3.0), 60),
+            Err(Error::InvalidTimestamp(60))
+        ));
+        assert!(resampler.partial().is_none());
+
+        assert_eq!(resampler.push(candle(4.0), 120).unwrap().count(), 0);
+        assert_eq!(resampler.flush(), Some((candle(4.0), 120)));
+        assert!(resampler.push(candle(5.0), 180).is_err());
+        assert_eq!(resampler.push(candle(6.0), 240).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,835 @@
+//! Rolling window statistics over columns, with streaming counterparts.
+
+use std::collections::VecDeque;
+
+use super::{Column, MaskedColumn, Numeric};
+
+/// Rolling window over the values of a column, created by [`Column::rolling`].
+///
+/// Every statistic returns a masked column aligned with the source: the value at
+/// index `i` covers the window ending at `i`, and the first `window - 1` values,
+/// whose windows are incomplete, are masked.
+///
+/// A NaN or infinite value only affects the windows that contain it, as in pandas:
+/// running sums skip non-finite values and recover once they leave the window.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::core::Column;
+///
+/// let closes = Column::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+/// let sma = closes.rolling(3).mean();
+///
+/// assert_eq!(sma.get(1), None);
+/// assert_eq!(sma.get(2), Some(2.0));
+/// assert_eq!(sma.last(), Some(4.0));
+/// assert_eq!(closes.rolling(3).max().last(), Some(5.0));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Rolling<'a, T> {
+    values: &'a [T],
+    window: usize,
+}
+
+impl<T> Column<T> {
+    /// Creates a rolling window of `window` values over the column.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn rolling(&self, window: usize) -> Rolling<'_, T> {
+        if window == 0 {
+            panic!("window must be greater than zero");
+        }
+
+        Rolling {
+            values: self.as_slice(),
+            window,
+        }
+    }
+}
+
+impl<T: Numeric> Rolling<'_, T> {
+    /// Returns the window length.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Rolling sum, O(1) per value.
+    pub fn sum(&self) -> MaskedColumn<T> {
+        self.stream(RollingSum::new(self.window), RollingSum::push)
+    }
+
+    /// Rolling arithmetic mean, O(1) per value.
+    pub fn mean(&self) -> MaskedColumn<T> {
+        self.stream(RollingMean::new(self.window), RollingMean::push)
+    }
+
+    /// Rolling linearly weighted mean, weighting the newest value highest, O(1) per value.
+    pub fn wma(&self) -> MaskedColumn<T> {
+        self.stream(RollingWma::new(self.window), RollingWma::push)
+    }
+
+    /// Rolling variance with `ddof` delta degrees of freedom, O(1) per value.
+    ///
+    /// Use `ddof = 1` for the sample variance (the pandas default) and `ddof = 0`
+    /// for the population variance. Windows with no more than `ddof` values are masked.
+    pub fn var(&self, ddof: usize) -> MaskedColumn<T> {
+        self.stream(RollingVar::new(self.window, ddof), RollingVar::push)
+    }
+
+    /// Rolling standard deviation with `ddof` delta degrees of freedom, O(1) per value.
+    pub fn std(&self, ddof: usize) -> MaskedColumn<T> {
+        self.stream(RollingVar::new(self.window, ddof), |state, value| {
+            state.push(value).map(Numeric::sqrt)
+        })
+    }
+
+    /// Rolling minimum, amortized O(1) per value.
+    pub fn min(&self) -> MaskedColumn<T> {
+        self.stream(RollingMin::new(self.window), RollingMin::push)
+    }
+
+    /// Rolling maximum, amortized O(1) per value.
+    pub fn max(&self) -> MaskedColumn<T> {
+        self.stream(RollingMax::new(self.window), RollingMax::push)
+    }
+
+    /// Rolling median, O(window) per value.
+    pub fn median(&self) -> MaskedColumn<T> {
+        self.stream(RollingMedian::new(self.window), RollingMedian::push)
+    }
+
+    /// Applies a function to every complete window, O(window) per value.
+    pub fn apply<U: Default, F>(&self, mut f: F) -> MaskedColumn<U>
+    where
+        F: FnMut(&[T]) -> U,
+    {
+        let incomplete = (self.window - 1).min(self.values.len());
+        (0..incomplete)
+            .map(|_| None)
+            .chain(
+                self.values
+                    .windows(self.window)
+                    .map(|window| Some(f(window))),
+            )
+            .collect()
+    }
+
+    /// Feeds every value through a streaming state.
+    fn stream<S, F>(&self, mut state: S, mut push: F) -> MaskedColumn<T>
+    where
+        F: FnMut(&mut S, T) -> Option<T>,
+    {
+        self.values
+            .iter()
+            .map(|value| push(&mut state, *value))
+            .collect()
+    }
+}
+
+/// Streaming rolling sum.
+#[derive(Debug, Clone)]
+pub struct RollingSum<T> {
+    window: usize,
+    values: VecDeque<T>,
+    // Sum of the finite values only, so a NaN or infinity cannot poison it
+    sum: T,
+    non_finite: usize,
+}
+
+impl<T: Numeric> RollingSum<T> {
+    /// Creates an empty rolling sum over `window` values.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        if window == 0 {
+            panic!("window must be greater than zero");
+        }
+
+        Self {
+            window,
+            values: VecDeque::with_capacity(window + 1),
+            sum: T::ZERO,
+            non_finite: 0,
+        }
+    }
+
+    /// Pushes a value and returns the sum of the window, or None until the window is full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.values.push_back(value);
+        if value.is_finite() {
+            self.sum += value;
+        } else {
+            self.non_finite += 1;
+        }
+        if self.values.len() > self.window
+            && let Some(old) = self.values.pop_front()
+        {
+            if old.is_finite() {
+                self.sum -= old;
+            } else {
+                self.non_finite -= 1;
+            }
+        }
+        self.value()
+    }
+
+    /// Returns the sum of the window, or None until the window is full.
+    ///
+    /// While the window holds a non-finite value, the sum is recomputed from the
+    /// window so that it is NaN or infinite like the plain sum.
+    pub fn value(&self) -> Option<T> {
+        self.is_ready().then(|| self.total())
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.values.len() == self.window
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.sum = T::ZERO;
+        self.non_finite = 0;
+    }
+
+    /// Sum of the values in the window, full or not.
+    fn total(&self) -> T {
+        if self.non_finite > 0 {
+            self.values.iter().copied().sum()
+        } else {
+            self.sum
+        }
+    }
+}
+
+/// Streaming rolling arithmetic mean.
+#[derive(Debug, Clone)]
+pub struct RollingMean<T> {
+    sum: RollingSum<T>,
+}
+
+impl<T: Numeric> RollingMean<T> {
+    /// Creates an empty rolling mean over `window` values.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        Self {
+            sum: RollingSum::new(window),
+        }
+    }
+
+    /// Pushes a value and returns the mean of the window, or None until the window is full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.sum.push(value);
+        self.value()
+    }
+
+    /// Returns the mean of the window, or None until the window is full.
+    pub fn value(&self) -> Option<T> {
+        let sum = self.sum.value()?;
+        Some(sum / T::from_usize(self.sum.window))
+    }
+
+    /// Returns the mean of the values pushed so far, or None if there are none.
+    pub(crate) fn partial(&self) -> Option<T> {
+        let len = self.sum.values.len();
+        (len > 0).then(|| self.sum.total() / T::from_usize(len))
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.sum.is_ready()
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.sum.reset();
+    }
+}
+
+/// Streaming rolling linearly weighted mean.
+///
+/// The newest of `n` values has weight `n` and the oldest weight 1.
+#[derive(Debug, Clone)]
+pub struct RollingWma<T> {
+    sum: RollingSum<T>,
+    // Sum of the finite values weighted by their position in the window
+    weighted_sum: T,
+}
+
+impl<T: Numeric> RollingWma<T> {
+    /// Creates an empty rolling weighted mean over `window` values.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        Self {
+            sum: RollingSum::new(window),
+            weighted_sum: T::ZERO,
+        }
+    }
+
+    /// Pushes a value and returns the weighted mean of the window, or None until the
+    /// window is full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        if self.sum.is_ready() {
+            // Every weight drops by one, removing the oldest value entirely
+            self.weighted_sum -= self.sum.sum;
+        }
+        self.sum.push(value);
+        if value.is_finite() {
+            self.weighted_sum += T::from_usize(self.sum.values.len()) * value;
+        }
+        self.value()
+    }
+
+    /// Returns the weighted mean of the window, or None until the window is full.
+    pub fn value(&self) -> Option<T> {
+        self.is_ready().then(|| self.mean())
+    }
+
+    /// Returns the weighted mean of the values pushed so far, or None if there are none.
+    pub(crate) fn partial(&self) -> Option<T> {
+        (!self.sum.values.is_empty()).then(|| self.mean())
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.sum.is_ready()
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.sum.reset();
+        self.weighted_sum = T::ZERO;
+    }
+
+    fn mean(&self) -> T {
+        let values = &self.sum.values;
+        let weighted_sum = if self.sum.non_finite > 0 {
+            (1..=values.len())
+                .zip(values)
+                .map(|(weight, value)| T::from_usize(weight) * *value)
+                .sum()
+        } else {
+            self.weighted_sum
+        };
+        weighted_sum / T::from_usize(values.len() * (values.len() + 1) / 2)
+    }
+}
+
+/// Streaming rolling variance, updated with Welford's algorithm.
+///
+/// The running moments are suspended while the window holds a NaN or infinity
+/// and recomputed once the last one leaves it.
+#[derive(Debug, Clone)]
+pub struct RollingVar<T> {
+    window: usize,
+    ddof: usize,
+    values: VecDeque<T>,
+    mean: T,
+    m2: T,
+    non_finite: usize,
+}
+
+impl<T: Numeric> RollingVar<T> {
+    /// Creates an empty rolling variance over `window` values with `ddof`
+    /// delta degrees of freedom.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize, ddof: usize) -> Self {
+        if window == 0 {
+            panic!("window must be greater than zero");
+        }
+
+        Self {
+            window,
+            ddof,
+            values: VecDeque::with_capacity(window),
+            mean: T::ZERO,
+            m2: T::ZERO,
+            non_finite: 0,
+        }
+    }
+
+    /// Pushes a value and returns the variance of the window, or None until the window is full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let old = if self.values.len() == self.window {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        self.values.push_back(value);
+        if !value.is_finite() {
+            self.non_finite += 1;
+        }
+        if let Some(old) = old
+            && !old.is_finite()
+        {
+            self.non_finite -= 1;
+            if self.non_finite == 0 {
+                (self.mean, self.m2) = moments(&self.values);
+                return self.value();
+            }
+        }
+        if self.non_finite > 0 {
+            return self.value();
+        }
+
+        match old {
+            Some(old) => {
+                let mean = self.mean + (value - old) / T::from_usize(self.window);
+                self.m2 += (value - old) * (value - mean + old - self.mean);
+                self.mean = mean;
+            }
+            None => {
+                let delta = value - self.mean;
+                self.mean += delta / T::from_usize(self.values.len());
+                self.m2 += delta * (value - self.mean);
+            }
+        }
+        self.value()
+    }
+
+    /// Returns the variance of the window, or None until the window is full.
+    pub fn value(&self) -> Option<T> {
+        if !self.is_ready() || self.window <= self.ddof {
+            return None;
+        }
+        let m2 = if self.non_finite > 0 {
+            moments(&self.values).1
+        } else {
+            // Rounding can push the sum of squares slightly below zero
+            self.m2.max(T::ZERO)
+        };
+        Some(m2 / T::from_usize(self.window - self.ddof))
+    }
+
+    /// Returns the standard deviation of the window, or None until the window is full.
+    pub fn std(&self) -> Option<T> {
+        self.value().map(Numeric::sqrt)
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.values.len() == self.window
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.mean = T::ZERO;
+        self.m2 = T::ZERO;
+        self.non_finite = 0;
+    }
+}
+
+/// Returns the mean and the sum of squared deviations of a non-empty window.
+fn moments<T: Numeric>(values: &VecDeque<T>) -> (T, T) {
+    let mean = values.iter().copied().sum::<T>() / T::from_usize(values.len());
+    let m2 = values
+        .iter()
+        .map(|value| (*value - mean) * (*value - mean))
+        .sum();
+    (mean, m2)
+}
+
+/// Streaming rolling extreme backed by a monotonic deque.
+#[derive(Debug, Clone)]
+struct MonotonicDeque<T> {
+    window: usize,
+    count: usize,
+    candidates: VecDeque<(usize, T)>,
+}
+
+impl<T: Numeric> MonotonicDeque<T> {
+    fn new(window: usize) -> Self {
+        if window == 0 {
+            panic!("window must be greater than zero");
+        }
+
+        Self {
+            window,
+            count: 0,
+            candidates: VecDeque::new(),
+        }
+    }
+
+    /// Pushes a value, dropping candidates for which `dominates(value, candidate)` holds.
+    fn push<F>(&mut self, value: T, dominates: F) -> Option<T>
+    where
+        F: Fn(T, T) -> bool,
+    {
+        while let Some(&(_, back)) = self.candidates.back() {
+            if !dominates(value, back) {
+                break;
+            }
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back((self.count, value));
+        self.count += 1;
+
+        while let Some(&(index, _)) = self.candidates.front() {
+            if index + self.window >= self.count {
+                break;
+            }
+            self.candidates.pop_front();
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        self.candidates.front().map(|&(_, value)| value)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.window
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.candidates.clear();
+    }
+}
+
+/// Streaming rolling minimum, amortized O(1) per value.
+#[derive(Debug, Clone)]
+pub struct RollingMin<T> {
+    deque: MonotonicDeque<T>,
+}
+
+impl<T: Numeric> RollingMin<T> {
+    /// Creates an empty rolling minimum over `window` values.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        Self {
+            deque: MonotonicDeque::new(window),
+        }
+    }
+
+    /// Pushes a value and returns the minimum of the window, or None until the window is full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.deque
+            .push(value, |value, candidate| value <= candidate)
+    }
+
+    /// Returns the minimum of the window, or None until the window is full.
+    pub fn value(&self) -> Option<T> {
+        self.deque.value()
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.deque.is_ready()
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.deque.reset();
+    }
+}
+
+/// Streaming rolling maximum, amortized O(1) per value.
+#[derive(Debug, Clone)]
+pub struct RollingMax<T> {
+    deque: MonotonicDeque<T>,
+}
+
+impl<T: Numeric> RollingMax<T> {
+    /// Creates an empty rolling maximum over `window` values.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        Self {
+            deque: MonotonicDeque::new(window),
+        }
+    }
+
+    /// Pushes a value and returns the maximum of the window, or None until the window is full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.deque
+            .push(value, |value, candidate| value >= candidate)
+    }
+
+    /// Returns the maximum of the window, or None until the window is full.
+    pub fn value(&self) -> Option<T> {
+        self.deque.value()
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.deque.is_ready()
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.deque.reset();
+    }
+}
+
+/// Streaming rolling median, O(window) per value.
+///
+/// The window is kept sorted alongside the arrival order; for an even window the
+/// median is the mean of the two middle values. NaN sorts after every other
+/// value, and the median of a window holding NaN is NaN.
+#[derive(Debug, Clone)]
+pub struct RollingMedian<T> {
+    window: usize,
+    values: VecDeque<T>,
+    sorted: Vec<T>,
+    nan: usize,
+}
+
+impl<T: Numeric> RollingMedian<T> {
+    /// Creates an empty rolling median over `window` values.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        if window == 0 {
+            panic!("window must be greater than zero");
+        }
+
+        Self {
+            window,
+            values: VecDeque::with_capacity(window + 1),
+            sorted: Vec::with_capacity(window + 1),
+            nan: 0,
+        }
+    }
+
+    /// Pushes a value and returns the median of the window, or None until the window is full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.values.push_back(value);
+        let index = self.sorted.partition_point(|v| sorts_before(*v, value));
+        self.sorted.insert(index, value);
+        self.nan += usize::from(is_nan(value));
+
+        if self.values.len() > self.window
+            && let Some(old) = self.values.pop_front()
+        {
+            let index = self.sorted.partition_point(|v| sorts_before(*v, old));
+            self.sorted.remove(index);
+            self.nan -= usize::from(is_nan(old));
+        }
+        self.value()
+    }
+
+    /// Returns the median of the window, or None until the window is full.
+    pub fn value(&self) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        if self.nan > 0 {
+            return self.sorted.last().copied();
+        }
+        let mid = self.window / 2;
+        if self.window.is_multiple_of(2) {
+            Some((self.sorted[mid - 1] + self.sorted[mid]) / T::two())
+        } else {
+            Some(self.sorted[mid])
+        }
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.values.len() == self.window
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.sorted.clear();
+        self.nan = 0;
+    }
+}
+
+/// Returns true if `value` is NaN, the only value not comparable to itself.
+fn is_nan<T: Numeric>(value: T) -> bool {
+    value.partial_cmp(&value).is_none()
+}
+
+/// Total order of a sorted window, placing NaN after every other value.
+fn sorts_before<T: Numeric>(a: T, b: T) -> bool {
+    !is_nan(a) && (is_nan(b) || a < b)
+}
+
+/// Streaming rolling application of a function to every complete window.
+#[derive(Debug, Clone)]
+pub struct RollingApply<T, F> {
+    window: usize,
+    values: VecDeque<T>,
+    f: F,
+}
+
+impl<T: Numeric, U, F> RollingApply<T, F>
+where
+    F: FnMut(&[T]) -> U,
+{
+    /// Creates an empty rolling window of `window` values that applies `f` to each full window.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(window: usize, f: F) -> Self {
+        if window == 0 {
+            panic!("window must be greater than zero");
+        }
+
+        Self {
+            window,
+            values: VecDeque::with_capacity(window + 1),
+            f,
+        }
+    }
+
+    /// Pushes a value and returns `f` applied to the window, or None until the window is full.
+    pub fn push(&mut self, value: T) -> Option<U> {
+        self.values.push_back(value);
+        if self.values.len() > self.window {
+            self.values.pop_front();
+        }
+        if !self.is_ready() {
+            return None;
+        }
+        Some((self.f)(self.values.make_contiguous()))
+    }
+
+    /// Returns true once the window is full.
+    pub fn is_ready(&self) -> bool {
+        self.values.len() == self.window
+    }
+
+    /// Clears all values.
+    pub fn reset(&mut self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{CLOSES, assert_close};
+
+    #[test]
+    fn test_rolling_matches_naive() {
+        let values = Column::from(CLOSES[..12].to_vec());
+        let rolling = values.rolling(4);
+
+        let naive = |f: &dyn Fn(&[f64]) -> f64| -> Vec<Option<f64>> {
+            (0..values.len())
+                .map(|i| (i >= 3).then(|| f(&values[i - 3..i + 1])))
+                .collect()
+        };
+        let mean = |w: &[f64]| w.iter().sum::<f64>() / w.len() as f64;
+        let var = |w: &[f64], ddof: usize| {
+            let m = mean(w);
+            w.iter().map(|v| (v - m) * (v - m)).sum::<f64>() / (w.len() - ddof) as f64
+        };
+
+        assert_close(&rolling.sum(), &naive(&|w| w.iter().sum()));
+        assert_close(&rolling.mean(), &naive(&mean));
+        assert_close(
+            &rolling.wma(),
+            &naive(&|w| (w[0] + 2.0 * w[1] + 3.0 * w[2] + 4.0 * w[3]) / 10.0),
+        );
+        assert_close(&rolling.var(1), &naive(&|w| var(w, 1)));
+        assert_close(&rolling.var(0), &naive(&|w| var(w, 0)));
+        assert_close(&rolling.std(1), &naive(&|w| var(w, 1).sqrt()));
+        assert_close(
+            &rolling.min(),
+            &naive(&|w| w.iter().copied().fold(f64::MAX, f64::min)),
+        );
+        assert_close(
+            &rolling.max(),
+            &naive(&|w| w.iter().copied().fold(f64::MIN, f64::max)),
+        );
+        assert_close(
+            &rolling.median(),
+            &naive(&|w| {
+                let mut sorted = w.to_vec();
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                (sorted[1] + sorted[2]) / 2.0
+            }),
+        );
+        assert_close(&rolling.apply(|w| w[3] - w[0]), &naive(&|w| w[3] - w[0]));
+    }
+
+    #[test]
+    fn test_streaming_matches_batch() {
+        let values = Column::from(CLOSES[..12].to_vec());
+        let expected = values.rolling(5).median();
+
+        let mut median = RollingMedian::new(5);
+        let mut max = RollingMax::new(5);
+        let mut apply = RollingApply::new(5, |w: &[f64]| w.len());
+        let mut wma = RollingWma::new(5);
+        for (i, value) in values.iter().enumerate() {
+            let partial = values.rolling(i.min(4) + 1).wma().get(i);
+            assert_eq!(wma.push(*value), (i >= 4).then_some(partial.unwrap()));
+            assert_eq!(wma.partial(), partial);
+            assert_eq!(median.push(*value), expected.get(i));
+            assert_eq!(max.push(*value), values.rolling(5).max().get(i));
+            assert_eq!(apply.push(*value), (i >= 4).then_some(5));
+        }
+
+        median.reset();
+        assert_eq!(median.value(), None);
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        // A NaN or infinity only affects the windows containing it
+        for poison in [f64::NAN, f64::INFINITY] {
+            let mut mean = RollingMean::new(2);
+            let means: Vec<_> = [1.0, poison, 1.0, 1.0, 1.0]
+                .into_iter()
+                .map(|value| mean.push(value))
+                .collect();
+            assert_eq!(means[0], None);
+            assert!(!means[1].unwrap().is_finite() && !means[2].unwrap().is_finite());
+            assert_eq!(&means[3..], &[Some(1.0), Some(1.0)]);
+
+            let values = Column::from(vec![1.0, 2.0, poison, 4.0, 5.0, 6.0, 7.0, 9.0]);
+            let var = values.rolling(3).var(1);
+            let median = values.rolling(3).median();
+            for i in 2..5 {
+                assert!(var.get(i).unwrap().is_nan(), "var at {i}");
+            }
+            assert_eq!(var.get(5), Some(1.0));
+            assert!((var.get(7).unwrap() - 7.0 / 3.0).abs() < 1e-12);
+            assert_eq!(median.get(5), Some(5.0));
+            assert_eq!(median.get(7), Some(7.0));
+            assert_eq!(values.rolling(3).sum().get(6), Some(18.0));
+            let wma = values.rolling(3).wma();
+            assert!(!wma.get(4).unwrap().is_finite());
+            assert_eq!(wma.get(5), Some((4.0 + 10.0 + 18.0) / 6.0));
+        }
+
+        // NaN sorts last, so it is removed from the sorted window exactly
+        let values = Column::from(vec![3.0, f64::NAN, 1.0, 2.0, 5.0, 4.0]);
+        let median = values.rolling(3).median();
+        assert!(median.get(2).unwrap().is_nan());
+        assert!(median.get(3).unwrap().is_nan());
+        assert_eq!(median.get(4), Some(2.0));
+        assert_eq!(median.get(5), Some(4.0));
+    }
+
+    #[test]
+    fn test_rolling_short_input() {
+        let values = Column::from(vec![1.0, 2.0]);
+        assert_eq!(values.rolling(3).mean().valid_count(), 0);
+        assert_eq!(values.rolling(3).apply(|w| w[0]).len(), 2);
+        assert_eq!(values.rolling(2).var(2).valid_count(), 0);
+    }
+}
@@ -0,0 +1,868 @@
+//! Candle timeframes with calendar alignment.
+//!
+//! Named constructors, parsing and calendar timeframes interpret timestamps as
+//! milliseconds since the Unix epoch. A plain length converted from `u64` only
+//! splits timestamps into equal buckets, so it works with timestamps in any unit.
+//!
+//! With the `chrono-tz` feature, daily, weekly and monthly timeframes can also
+//! follow the local midnight of an IANA timezone across daylight saving changes.
+
+use std::{fmt::Display, str::FromStr};
+
+use super::Error;
+
+const SECOND: i64 = 1_000;
+const MINUTE: i64 = 60 * SECOND;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+/// Offset of the first Monday after the Unix epoch (1970-01-01 was a Thursday).
+const MONDAY: i64 = 4 * DAY;
+
+/// Period used to bucket timestamps into candles.
+///
+/// Fixed timeframes split time into equal-length buckets whose boundaries are
+/// shifted by `offset` from the epoch; this covers intraday, daily and ISO weekly
+/// candles in any UTC offset. Monthly timeframes follow the calendar.
+///
+/// Timeframes can be parsed from strings made of a count, a unit (`ms`, `s`, `m`,
+/// `h`, `d`, `w` or `M` for months) and an optional UTC offset:
+///
+/// ```rust
+/// use mizuhiki_ta::core::Timeframe;
+///
+/// let daily: Timeframe = "1d+08:00".parse().unwrap();
+/// assert_eq!(daily, Timeframe::days(1).with_utc_offset(8 * 3_600_000));
+/// assert_eq!(daily.bucket_start(1_700_000_000_000), 1_699_977_600_000);
+/// ```
+///
+/// A fixed UTC offset does not follow daylight saving time. With the `chrono-tz`
+/// feature, days, weeks and months can instead be bound to a timezone with
+/// `@`, as in `"1d@America/New_York"`.
+///
+/// The enum is non-exhaustive, so enabling the feature does not break matches
+/// in other crates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Timeframe {
+    /// Buckets of `length` milliseconds starting at `offset + k * length`, with
+    /// `offset` normalized into `[0, length)`.
+    Fixed { length: u64, offset: i64 },
+    /// Buckets of `count` calendar months, with boundaries at local midnight
+    /// where local time is `ts - offset`.
+    Months { count: u32, offset: i64 },
+    /// Bars that are not bucketed by time, such as tick or volume bars.
+    /// Each bar lasts until the next one starts.
+    Irregular,
+    /// Buckets of `count` days, ISO weeks or calendar months with boundaries at
+    /// local midnight in `tz`, so their length follows daylight saving changes.
+    #[cfg(feature = "chrono-tz")]
+    Zoned {
+        count: u32,
+        unit: CalendarUnit,
+        tz: chrono_tz::Tz,
+    },
+}
+
+/// Calendar unit of a timeframe bound to a timezone.
+#[cfg(feature = "chrono-tz")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarUnit {
+    Day,
+    Week,
+    Month,
+}
+
+impl Timeframe {
+    /// Creates a fixed timeframe of `length` milliseconds aligned to the epoch.
+    ///
+    /// # Panics
+    /// Panics if `length` is zero or greater than `i64::MAX`.
+    pub fn millis(length: u64) -> Self {
+        Self::fixed(length, 1)
+    }
+
+    /// Creates a timeframe of `count` seconds.
+    ///
+    /// # Panics
+    /// Panics if `count` is zero or the length in milliseconds is greater than
+    /// `i64::MAX`, as do the other fixed constructors.
+    pub fn seconds(count: u64) -> Self {
+        Self::fixed(count, SECOND)
+    }
+
+    /// Creates a timeframe of `count` minutes.
+    pub fn minutes(count: u64) -> Self {
+        Self::fixed(count, MINUTE)
+    }
+
+    /// Creates a timeframe of `count` hours.
+    pub fn hours(count: u64) -> Self {
+        Self::fixed(count, HOUR)
+    }
+
+    /// Creates a timeframe of `count` days starting at midnight UTC.
+    pub fn days(count: u64) -> Self {
+        Self::fixed(count, DAY)
+    }
+
+    /// Creates a timeframe of `count` ISO weeks starting on Monday at midnight UTC.
+    pub fn weeks(count: u64) -> Self {
+        Self::fixed(count, WEEK).with_offset(MONDAY)
+    }
+
+    /// Creates a fixed timeframe of `count` units of `unit` milliseconds.
+    fn fixed(count: u64, unit: i64) -> Self {
+        if count == 0 {
+            panic!("timeframe length must be greater than zero");
+        }
+        Self::try_fixed(count, unit).expect("timeframe length must not exceed i64::MAX")
+    }
+
+    /// Creates a fixed timeframe of `count` units of `unit` milliseconds, or None if
+    /// the length is zero or does not fit in an `i64`.
+    fn try_fixed(count: u64, unit: i64) -> Option<Self> {
+        let length = count
+            .checked_mul(unit as u64)
+            .filter(|&length| length > 0 && length <= i64::MAX as u64)?;
+        Some(Timeframe::Fixed { length, offset: 0 })
+    }
+
+    /// Creates a timeframe of `count` calendar months starting on the 1st at midnight UTC.
+    ///
+    /// # Panics
+    /// Panics if `count` is zero.
+    pub fn months(count: u32) -> Self {
+        if count == 0 {
+            panic!("timeframe length must be greater than zero");
+        }
+        Timeframe::Months { count, offset: 0 }
+    }
+
+    /// Creates a timeframe of `count` calendar units starting at local midnight in
+    /// `tz`. Weeks start on Monday.
+    ///
+    /// # Panics
+    /// Panics if `count` is zero.
+    #[cfg(feature = "chrono-tz")]
+    pub fn zoned(count: u32, unit: CalendarUnit, tz: chrono_tz::Tz) -> Self {
+        if count == 0 {
+            panic!("timeframe length must be greater than zero");
+        }
+        Timeframe::Zoned { count, unit, tz }
+    }
+
+    /// Shifts all bucket boundaries by `offset` milliseconds.
+    ///
+    /// The offset of a monthly timeframe saturates at the bounds of `i64`. Zoned
+    /// timeframes keep their local midnight boundaries and are returned unchanged.
+    pub fn with_offset(self, offset: i64) -> Self {
+        match self {
+            Timeframe::Fixed {
+                length,
+                offset: current,
+            } if length > 0 => Timeframe::Fixed {
+                length,
+                offset: (current as i128 + offset as i128).rem_euclid(length as i128) as i64,
+            },
+            Timeframe::Fixed { .. } | Timeframe::Irregular => self,
+            #[cfg(feature = "chrono-tz")]
+            Timeframe::Zoned { .. } => self,
+            Timeframe::Months {
+                count,
+                offset: current,
+            } => Timeframe::Months {
+                count,
+                offset: current.saturating_add(offset),
+            },
+        }
+    }
+
+    /// Aligns bucket boundaries to local midnight for a UTC offset in milliseconds
+    /// (e.g. `8 * 3_600_000` for UTC+08:00).
+    pub fn with_utc_offset(self, utc_offset: i64) -> Self {
+        self.with_offset(utc_offset.saturating_neg())
+    }
+
+    /// Returns true if the timeframe has a zero length, so it cannot bucket timestamps.
+    ///
+    /// Constructors and parsing never produce empty timeframes, but the variants
+    /// can be built directly.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Timeframe::Fixed { length, .. } => *length == 0,
+            Timeframe::Months { count, .. } => *count == 0,
+            #[cfg(feature = "chrono-tz")]
+            Timeframe::Zoned { count, .. } => *count == 0,
+            Timeframe::Irregular => false,
+        }
+    }
+
+    /// Returns the start of the bucket containing `ts`.
+    ///
+    /// A fixed or monthly bucket that starts before the epoch reports a start of 0,
+    /// but [`Timeframe::next_start`] still returns its real end.
+    ///
+    /// # Panics
+    /// Panics if the timeframe is empty.
+    pub fn bucket_start(&self, ts: u64) -> u64 {
+        match *self {
+            Timeframe::Fixed { length, offset } => {
+                clamp_timestamp(fixed_bucket_start(ts, length, offset))
+            }
+            Timeframe::Months { count, offset } => {
+                clamp_timestamp(month_start(month_bucket(ts, count, offset), offset))
+            }
+            Timeframe::Irregular => ts,
+            #[cfg(feature = "chrono-tz")]
+            Timeframe::Zoned { count, unit, tz } => {
+                let period = unit.period(zoned::local_days(ts, tz));
+                zoned::local_midnight(unit.start_day(period - period.rem_euclid(count as i64)), tz)
+            }
+        }
+    }
+
+    /// Returns the start of the bucket following the one starting at `start`.
+    ///
+    /// Saturates at `u64::MAX` if the next bucket starts beyond it.
+    pub fn next_start(&self, start: u64) -> u64 {
+        match *self {
+            Timeframe::Fixed { length, offset } => {
+                clamp_timestamp(fixed_bucket_start(start, length, offset) + length as i128)
+            }
+            Timeframe::Months { count, offset } => {
+                let next = month_bucket(start, count, offset) + count as i64;
+                clamp_timestamp(month_start(next, offset))
+            }
+            Timeframe::Irregular => u64::MAX,
+            #[cfg(feature = "chrono-tz")]
+            Timeframe::Zoned { count, unit, tz } => {
+                let period = unit.period(zoned::local_days(self.bucket_start(start), tz));
+                zoned::local_midnight(unit.start_day(period + count as i64), tz)
+            }
+        }
+    }
+
+    /// Returns true if every boundary of this timeframe is also a boundary of `source`,
+    /// meaning candles of `source` can be merged into candles of this timeframe.
+    pub fn is_multiple_of(&self, source: &Timeframe) -> bool {
+        if self.is_empty() || source.is_empty() {
+            return false;
+        }
+
+        match (*self, *source) {
+            (
+                Timeframe::Fixed { length, offset },
+                Timeframe::Fixed {
+                    length: source_length,
+                    offset: source_offset,
+                },
+            ) => {
+                length.is_multiple_of(source_length)
+                    && (offset as i128 - source_offset as i128).rem_euclid(source_length as i128)
+                        == 0
+            }
+            (
+                Timeframe::Months { offset, .. },
+                Timeframe::Fixed {
+                    length: source_length,
+                    offset: source_offset,
+                },
+            ) => {
+                // Month boundaries fall on local midnights
+                (DAY as u64).is_multiple_of(source_length)
+                    && (offset as i128 - source_offset as i128).rem_euclid(source_length as i128)
+                        == 0
+            }
+            (
+                Timeframe::Months { count, offset },
+                Timeframe::Months {
+                    count: source_count,
+                    offset: source_offset,
+                },
+            ) => count.is_multiple_of(source_count) && offset == source_offset,
+            (Timeframe::Fixed { .. }, Timeframe::Months { .. }) => false,
+            (Timeframe::Irregular, _) | (_, Timeframe::Irregular) => false,
+            // Local midnights fall on whole hours in most timezones; a source that
+            // straddles one anyway is rejected when resampling
+            #[cfg(feature = "chrono-tz")]
+            (
+                Timeframe::Zoned { .. },
+                Timeframe::Fixed {
+                    length: source_length,
+                    offset: source_offset,
+                },
+            ) => (HOUR as u64).is_multiple_of(source_length) && source_offset == 0,
+            #[cfg(feature = "chrono-tz")]
+            (
+                Timeframe::Zoned { count, unit, tz },
+                Timeframe::Zoned {
+                    count: source_count,
+                    unit: source_unit,
+                    tz: source_tz,
+                },
+            ) => {
+                tz == source_tz
+                    && match (unit, source_unit) {
+                        (CalendarUnit::Week | CalendarUnit::Month, CalendarUnit::Day) => {
+                            source_count == 1
+                        }
+                        _ => unit == source_unit && count.is_multiple_of(source_count),
+                    }
+            }
+            #[cfg(feature = "chrono-tz")]
+            (Timeframe::Zoned { .. }, Timeframe::Months { .. })
+            | (Timeframe::Fixed { .. } | Timeframe::Months { .. }, Timeframe::Zoned { .. }) => {
+                false
+            }
+        }
+    }
+}
+
+impl From<u64> for Timeframe {
+    /// Creates a fixed timeframe of the given length aligned to zero.
+    ///
+    /// Buckets start at multiples of `length`, so the length is in the same unit as
+    /// the timestamps it is used with: `CandleSeries::new(60)` builds one-minute
+    /// candles from timestamps in seconds and 60 ms candles from timestamps in
+    /// milliseconds. Only [`Display`] assumes milliseconds.
+    ///
+    /// # Panics
+    /// Panics if `length` is zero or greater than `i64::MAX`.
+    fn from(length: u64) -> Self {
+        Timeframe::millis(length)
+    }
+}
+
+impl FromStr for Timeframe {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::ParseTimeframe(s.to_string());
+
+        // Timezone names may contain signs, so they are split off first
+        #[cfg(feature = "chrono-tz")]
+        if let Some((period, tz)) = s.split_once('@') {
+            let tz: chrono_tz::Tz = tz.parse().map_err(|_| invalid())?;
+            let digits = period
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(invalid)?;
+            let count: u32 = period[..digits].parse().map_err(|_| invalid())?;
+            let unit = match &period[digits..] {
+                "d" => CalendarUnit::Day,
+                "w" => CalendarUnit::Week,
+                "M" => CalendarUnit::Month,
+                _ => return Err(invalid()),
+            };
+            if count == 0 {
+                return Err(invalid());
+            }
+            return Ok(Timeframe::Zoned { count, unit, tz });
+        }
+
+        let (period, utc_offset) = match s.find(['+', '-']) {
+            Some(i) => (&s[..i], Some(&s[i..])),
+            None => (s, None),
+        };
+
+        let digits = period
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let count: u64 = period[..digits].parse().map_err(|_| invalid())?;
+        if count == 0 {
+            return Err(invalid());
+        }
+
+        let timeframe = match &period[digits..] {
+            "ms" => Timeframe::try_fixed(count, 1),
+            "s" => Timeframe::try_fixed(count, SECOND),
+            "m" => Timeframe::try_fixed(count, MINUTE),
+            "h" => Timeframe::try_fixed(count, HOUR),
+            "d" => Timeframe::try_fixed(count, DAY),
+            "w" => Timeframe::try_fixed(count, WEEK).map(|weeks| weeks.with_offset(MONDAY)),
+            "M" => u32::try_from(count).ok().map(Timeframe::months),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+
+        match utc_offset {
+            Some(utc_offset) => {
+                Ok(timeframe.with_utc_offset(parse_utc_offset(utc_offset).ok_or_else(invalid)?))
+            }
+            None => Ok(timeframe),
+        }
+    }
+}
+
+impl Display for Timeframe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let utc_offset = match *self {
+            Timeframe::Fixed { length, offset } => {
+                let length = length as i64;
+                // UTC offset of the boundaries relative to `base`, normalized to the nearest one
+                let utc_offset = |base: i64| {
+                    let utc = (base - offset).rem_euclid(length.max(1));
+                    if utc > length / 2 { utc - length } else { utc }
+                };
+
+                let weekly =
+                    length > 0 && length % WEEK == 0 && utc_offset(MONDAY).abs() <= 14 * HOUR;
+                let (unit, size, base) = if weekly {
+                    ("w", WEEK, MONDAY)
+                } else {
+                    [("d", DAY), ("h", HOUR), ("m", MINUTE), ("s", SECOND)]
+                        .into_iter()
+                        .find(|(_, size)| length > 0 && length % size == 0)
+                        .map_or(("ms", 1, 0), |(unit, size)| (unit, size, 0))
+                };
+                write!(f, "{}{}", length / size, unit)?;
+                utc_offset(base)
+            }
+            Timeframe::Months { count, offset } => {
+                write!(f, "{count}M")?;
+                offset.saturating_neg()
+            }
+            Timeframe::Irregular => return write!(f, "irregular"),
+            #[cfg(feature = "chrono-tz")]
+            Timeframe::Zoned { count, unit, tz } => {
+                let unit = match unit {
+                    CalendarUnit::Day => "d",
+                    CalendarUnit::Week => "w",
+                    CalendarUnit::Month => "M",
+                };
+                return write!(f, "{count}{unit}@{}", tz.name());
+            }
+        };
+
+        if utc_offset != 0 {
+            let sign = if utc_offset < 0 { '-' } else { '+' };
+            let minutes = utc_offset.abs() / MINUTE;
+            write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a UTC offset such as `+08:00` or `-05:30` into milliseconds.
+fn parse_utc_offset(s: &str) -> Option<i64> {
+    let sign = match s.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let (hours, minutes) = s[1..].split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    Some(sign * (hours * HOUR + minutes * MINUTE))
+}
+
+/// Returns the start of the fixed bucket containing `ts`, which is negative for a
+/// bucket that starts before the epoch.
+fn fixed_bucket_start(ts: u64, length: u64, offset: i64) -> i128 {
+    let ts = ts as i128;
+    ts - (ts - offset as i128).rem_euclid(length as i128)
+}
+
+/// Returns the index, `year * 12 + month0`, of the first month of the monthly
+/// bucket containing `ts`.
+fn month_bucket(ts: u64, count: u32, offset: i64) -> i64 {
+    let local_days = (ts as i128 - offset as i128).div_euclid(DAY as i128) as i64;
+    let (year, month, _) = civil_from_days(local_days);
+    let index = year * 12 + (month as i64 - 1);
+    index - index.rem_euclid(count as i64)
+}
+
+/// Returns the timestamp of the first day of the month with index `year * 12 + month0`,
+/// which is negative for a month that starts before the epoch.
+fn month_start(index: i64, offset: i64) -> i128 {
+    let year = index.div_euclid(12);
+    let month = index.rem_euclid(12) as u32 + 1;
+    days_from_civil(year, month, 1) as i128 * DAY as i128 + offset as i128
+}
+
+/// Converts a boundary into a timestamp, saturating at 0 and `u64::MAX`.
+fn clamp_timestamp(ts: i128) -> u64 {
+    ts.clamp(0, u64::MAX as i128) as u64
+}
+
+/// Converts a civil date into days since the Unix epoch.
+///
+/// Algorithm from Howard Hinnant's `days_from_civil`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Converts days since the Unix epoch into a civil date `(year, month, day)`.
+///
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(feature = "chrono-tz")]
+impl CalendarUnit {
+    /// Returns the index of the day, week or month containing a local day.
+    fn period(self, days: i64) -> i64 {
+        match self {
+            CalendarUnit::Day => days,
+            CalendarUnit::Week => (days - MONDAY / DAY).div_euclid(7),
+            CalendarUnit::Month => {
+                let (year, month, _) = civil_from_days(days);
+                year * 12 + (month as i64 - 1)
+            }
+        }
+    }
+
+    /// Returns the first local day of the period with the given index.
+    fn start_day(self, period: i64) -> i64 {
+        match self {
+            CalendarUnit::Day => period,
+            CalendarUnit::Week => period * 7 + MONDAY / DAY,
+            CalendarUnit::Month => {
+                days_from_civil(period.div_euclid(12), period.rem_euclid(12) as u32 + 1, 1)
+            }
+        }
+    }
+}
+
+/// Conversions between timestamps and local days in an IANA timezone.
+#[cfg(feature = "chrono-tz")]
+mod zoned {
+    use chrono::{DateTime, NaiveDateTime, Offset, TimeDelta, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{DAY, SECOND};
+
+    /// Returns the local day, in days since the Unix epoch, containing `ts`.
+    pub(super) fn local_days(ts: u64, tz: Tz) -> i64 {
+        let utc = i64::try_from(ts)
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        let offset = tz.offset_from_utc_datetime(&utc.naive_utc()).fix();
+        (utc.timestamp_millis() + offset.local_minus_utc() as i64 * SECOND).div_euclid(DAY)
+    }
+
+    /// Returns the timestamp of local midnight on a day, or of the first local time
+    /// after it when midnight is skipped by a daylight saving change.
+    pub(super) fn local_midnight(days: i64, tz: Tz) -> u64 {
+        let midnight = days
+            .checked_mul(DAY)
+            .and_then(DateTime::from_timestamp_millis)
+            .map_or(NaiveDateTime::MAX, |midnight| midnight.naive_utc());
+        (0..=2 * 24 * 4)
+            .find_map(|step| {
+                let local = midnight.checked_add_signed(TimeDelta::minutes(15 * step))?;
+                tz.from_local_datetime(&local).earliest()
+            })
+            .map_or(u64::MAX, |start| start.timestamp_millis().max(0) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2023-11-14T22:13:20Z, a Tuesday
+    const TS: u64 = 1_700_000_000_000;
+
+    #[test]
+    fn test_parse_and_display() {
+        for s in [
+            "15m", "4h", "1d", "1d+08:00", "1d-05:30", "1w", "2w+09:00", "1M", "3M+01:00", "500ms",
+            "30s",
+        ] {
+            let timeframe: Timeframe = s.parse().unwrap();
+            assert_eq!(timeframe.to_string(), s);
+        }
+
+        assert_eq!(
+            "15m".parse::<Timeframe>().unwrap(),
+            Timeframe::millis(900_000)
+        );
+        assert_eq!(Timeframe::from(60_000), Timeframe::minutes(1));
+        assert_eq!(
+            Timeframe::days(1).with_utc_offset(HOUR),
+            Timeframe::Fixed {
+                length: DAY as u64,
+                offset: DAY - HOUR,
+            }
+        );
+        for s in [
+            "",
+            "m",
+            "0m",
+            "15",
+            "15x",
+            "1d+8",
+            "1d+08:60",
+            "1d+15:00",
+            "99999999999999999w",
+            "18446744073709551615s",
+            "9223372036854775808ms",
+            "4294967296M",
+        ] {
+            assert!(
+                matches!(s.parse::<Timeframe>(), Err(Error::ParseTimeframe(_))),
+                "{s} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixed_buckets() {
+        let hourly = Timeframe::hours(4);
+        assert_eq!(hourly.bucket_start(TS), 1_699_992_000_000); // 20:00Z
+        assert_eq!(hourly.next_start(1_699_992_000_000), 1_700_006_400_000);
+
+        // Midnight in UTC+08:00 is 16:00Z on the previous day
+        let daily: Timeframe = "1d+08:00".parse().unwrap();
+        assert_eq!(daily.bucket_start(TS), 1_699_977_600_000); // 2023-11-14T16:00Z
+
+        // ISO weeks start on Monday 2023-11-13
+        let weekly = Timeframe::weeks(1);
+        assert_eq!(weekly.bucket_start(TS), 1_699_833_600_000);
+        assert_eq!(weekly.next_start(1_699_833_600_000), 1_700_438_400_000);
+
+        // The first daily bucket in UTC+08:00 starts before the epoch and ends at 16:00Z
+        assert_eq!(daily.bucket_start(HOUR as u64), 0);
+        assert_eq!(daily.next_start(0), 16 * HOUR as u64);
+        assert_eq!(daily.next_start(HOUR as u64), 16 * HOUR as u64);
+
+        // Timestamps beyond i64::MAX do not wrap
+        let minute = Timeframe::minutes(1);
+        let ts = u64::MAX - 1;
+        let start = ts - ts % MINUTE as u64;
+        assert_eq!(minute.bucket_start(ts), start);
+        assert_eq!(minute.next_start(ts), u64::MAX);
+        assert_eq!(minute.next_start(start - 1), start);
+    }
+
+    #[test]
+    fn test_month_buckets() {
+        let monthly = Timeframe::months(1);
+        assert_eq!(monthly.bucket_start(TS), 1_698_796_800_000); // 2023-11-01
+        assert_eq!(monthly.next_start(1_698_796_800_000), 1_701_388_800_000); // 2023-12-01
+
+        let quarterly = Timeframe::months(3);
+        assert_eq!(quarterly.bucket_start(TS), 1_696_118_400_000); // 2023-10-01
+        assert_eq!(quarterly.next_start(1_696_118_400_000), 1_704_067_200_000); // 2024-01-01
+
+        // Leap years
+        let feb = days_from_civil(2024, 2, 1) as u64 * DAY as u64;
+        assert_eq!(
+            monthly.next_start(feb),
+            days_from_civil(2024, 3, 1) as u64 * DAY as u64
+        );
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_month_arithmetic_does_not_overflow() {
+        let monthly: Timeframe = "1M".parse().unwrap();
+        let shifted = monthly.with_offset(i64::MAX).with_offset(1);
+        assert_eq!(
+            shifted,
+            Timeframe::Months {
+                count: 1,
+                offset: i64::MAX
+            }
+        );
+        let _ = shifted.to_string();
+        assert!(!shifted.is_multiple_of(&Timeframe::hours(5)));
+
+        // Timestamps beyond i64::MAX do not wrap
+        let start = monthly.bucket_start(u64::MAX);
+        assert!(start > i64::MAX as u64);
+        assert_eq!(civil_from_days((start / DAY as u64) as i64).2, 1);
+        assert_eq!(monthly.next_start(start), u64::MAX);
+        assert_eq!(monthly.bucket_start(start), start);
+
+        // The first bucket in UTC+08:00 starts before the epoch and ends at 16:00Z
+        let monthly = monthly.with_utc_offset(8 * HOUR);
+        assert_eq!(monthly.bucket_start(HOUR as u64), 0);
+        let february = days_from_civil(1970, 1, 31) as u64 * DAY as u64 + 16 * HOUR as u64;
+        assert_eq!(monthly.next_start(0), february);
+        assert_eq!(
+            Timeframe::millis(1).with_offset(i64::MAX),
+            Timeframe::millis(1)
+        );
+    }
+
+    #[test]
+    fn test_is_multiple_of() {
+        let minute = Timeframe::minutes(1);
+        assert!(Timeframe::minutes(15).is_multiple_of(&minute));
+        assert!(!Timeframe::seconds(90).is_multiple_of(&minute));
+        assert!(Timeframe::weeks(1).is_multiple_of(&Timeframe::days(1)));
+        assert!(Timeframe::months(1).is_multiple_of(&Timeframe::hours(1)));
+        assert!(!Timeframe::months(1).is_multiple_of(&Timeframe::hours(5)));
+        assert!(Timeframe::months(3).is_multiple_of(&Timeframe::months(1)));
+        assert!(!Timeframe::days(1).is_multiple_of(&Timeframe::months(1)));
+        assert!(
+            !Timeframe::days(1)
+                .with_utc_offset(HOUR)
+                .is_multiple_of(&Timeframe::days(1))
+        );
+    }
+
+    #[test]
+    fn test_unitless_length() {
+        // Timestamps in seconds bucket like the plain `ts - ts % length`
+        let timeframe = Timeframe::from(60);
+        for ts in [0, 59, 60, 1_700_000_000, 1_700_000_059] {
+            assert_eq!(timeframe.bucket_start(ts), ts - ts % 60);
+        }
+        assert_eq!(timeframe.next_start(120), 180);
+    }
+
+    #[test]
+    fn test_empty_timeframes() {
+        let empty = Timeframe::Fixed {
+            length: 0,
+            offset: 0,
+        };
+        assert!(empty.is_empty());
+        assert!(!Timeframe::minutes(1).is_empty());
+        assert!(!empty.is_multiple_of(&Timeframe::minutes(1)));
+        for s in ["0ms", "0M"] {
+            assert!(matches!(
+                s.parse::<Timeframe>(),
+                Err(Error::ParseTimeframe(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn test_zoned_buckets() {
+        use chrono_tz::{America, Asia};
+
+        let day = |year, month, day| (days_from_civil(year, month, day) * DAY) as u64;
+        let hour = HOUR as u64;
+
+        // New York switched to daylight saving time on 2024-03-10, a 23-hour day
+        let daily = Timeframe::zoned(1, CalendarUnit::Day, America::New_York);
+        assert_eq!(
+            daily.bucket_start(day(2024, 3, 10) + 12 * hour),
+            day(2024, 3, 10) + 5 * hour
+        );
+        assert_eq!(
+            daily.bucket_start(day(2024, 3, 10) + 3 * hour),
+            day(2024, 3, 9) + 5 * hour
+        );
+        assert_eq!(
+            daily.next_start(day(2024, 3, 10) + 5 * hour),
+            day(2024, 3, 11) + 4 * hour
+        );
+
+        let weekly = Timeframe::zoned(1, CalendarUnit::Week, America::New_York);
+        assert_eq!(
+            weekly.bucket_start(day(2024, 3, 10) + 12 * hour),
+            day(2024, 3, 4) + 5 * hour
+        );
+        assert_eq!(
+            weekly.next_start(day(2024, 3, 4) + 5 * hour),
+            day(2024, 3, 11) + 4 * hour
+        );
+
+        let monthly = Timeframe::zoned(1, CalendarUnit::Month, America::New_York);
+        assert_eq!(
+            monthly.bucket_start(day(2024, 3, 15)),
+            day(2024, 3, 1) + 5 * hour
+        );
+        assert_eq!(
+            monthly.next_start(day(2024, 3, 1) + 5 * hour),
+            day(2024, 4, 1) + 4 * hour
+        );
+
+        // Sao Paulo skipped midnight on 2018-11-04, so that day started at 01:00 local
+        let daily = Timeframe::zoned(1, CalendarUnit::Day, America::Sao_Paulo);
+        assert_eq!(
+            daily.bucket_start(day(2018, 11, 4) + 12 * hour),
+            day(2018, 11, 4) + 3 * hour
+        );
+        assert_eq!(
+            daily.next_start(day(2018, 11, 3) + 3 * hour),
+            day(2018, 11, 4) + 3 * hour
+        );
+
+        let minute = Timeframe::minutes(1);
+        let daily = Timeframe::zoned(1, CalendarUnit::Day, Asia::Tokyo);
+        assert!(daily.is_multiple_of(&minute));
+        assert!(daily.is_multiple_of(&Timeframe::hours(1)));
+        assert!(!daily.is_multiple_of(&Timeframe::hours(2)));
+        assert!(!daily.is_multiple_of(&Timeframe::days(1)));
+        assert!(Timeframe::zoned(1, CalendarUnit::Week, Asia::Tokyo).is_multiple_of(&daily));
+        assert!(Timeframe::zoned(3, CalendarUnit::Day, Asia::Tokyo).is_multiple_of(&daily));
+        assert!(!Timeframe::zoned(1, CalendarUnit::Week, Asia::Seoul).is_multiple_of(&daily));
+        assert!(!Timeframe::days(1).is_multiple_of(&daily));
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn test_parse_zoned() {
+        for s in [
+            "1d@America/New_York",
+            "2w@Europe/London",
+            "3M@Asia/Tokyo",
+            "1d@America/Port-au-Prince",
+            "1d@Etc/GMT+5",
+        ] {
+            let timeframe: Timeframe = s.parse().unwrap();
+            assert_eq!(timeframe.to_string(), s);
+        }
+        assert_eq!(
+            "1d@America/New_York".parse::<Timeframe>().unwrap(),
+            Timeframe::zoned(1, CalendarUnit::Day, chrono_tz::America::New_York)
+        );
+        for s in [
+            "1h@UTC",
+            "0d@UTC",
+            "1d@Nowhere/City",
+            "1d+08:00@UTC",
+            "@UTC",
+        ] {
+            assert!(
+                matches!(s.parse::<Timeframe>(), Err(Error::ParseTimeframe(_))),
+                "{s} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "timeframe length must be greater than zero")]
+    fn test_zero_length_panics() {
+        let _ = Timeframe::from(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "timeframe length must not exceed i64::MAX")]
+    fn test_overflowing_length_panics() {
+        let _ = Timeframe::weeks(99_999_999_999_999_999);
+    }
+}
@@ -5,6 +5,11 @@ use std::{
 };
 
 /// Trait for numeric types that can be used in technical analysis calculations.
+///
+/// The math methods mirror their `f64` counterparts, except that `signum` returns
+/// zero for zero on every type. Floats follow IEEE semantics for out-of-domain
+/// inputs, e.g. `ln` of a negative value is NaN, while exact types such as
+/// `Decimal` and [`Fixed`](super::Fixed) panic instead.
 pub trait Numeric:
     Copy
     + Debug
@@ -31,6 +36,17 @@ pub trait Numeric:
     fn hundred() -> Self;
     fn abs(self) -> Self;
     fn max(self, other: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn signum(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn ln(self) -> Self;
+    fn exp(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn is_finite(self) -> bool;
+    fn from_usize(n: usize) -> Self;
+    fn from_f64(n: f64) -> Self;
+    fn to_f64(self) -> f64;
     fn is_positive(self) -> bool {
         self > Self::ZERO
     }
@@ -58,6 +74,39 @@ impl Numeric for f32 {
     fn max(self, other: Self) -> Self {
         self.max(other)
     }
+    fn min(self, other: Self) -> Self {
+        self.min(other)
+    }
+    fn signum(self) -> Self {
+        if self == 0.0 { 0.0 } else { self.signum() }
+    }
+    fn sqrt(self) -> Self {
+        self.sqrt()
+    }
+    fn ln(self) -> Self {
+        self.ln()
+    }
+    fn exp(self) -> Self {
+        self.exp()
+    }
+    fn powi(self, n: i32) -> Self {
+        self.powi(n)
+    }
+    fn powf(self, n: Self) -> Self {
+        self.powf(n)
+    }
+    fn is_finite(self) -> bool {
+        self.is_finite()
+    }
+    fn from_usize(n: usize) -> Self {
+        n as Self
+    }
+    fn from_f64(n: f64) -> Self {
+        n as Self
+    }
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
 }
 
 impl Numeric for f64 {
@@ -79,4 +128,136 @@ impl Numeric for f64 {
     fn max(self, other: Self) -> Self {
         self.max(other)
     }
+    fn min(self, other: Self) -> Self {
+        self.min(other)
+    }
+    fn signum(self) -> Self {
+        if self == 0.0 { 0.0 } else { self.signum() }
+    }
+    fn sqrt(self) -> Self {
+        self.sqrt()
+    }
+    fn ln(self) -> Self {
+        self.ln()
+    }
+    fn exp(self) -> Self {
+        self.exp()
+    }
+    fn powi(self, n: i32) -> Self {
+        self.powi(n)
+    }
+    fn powf(self, n: Self) -> Self {
+        self.powf(n)
+    }
+    fn is_finite(self) -> bool {
+        self.is_finite()
+    }
+    fn from_usize(n: usize) -> Self {
+        n as Self
+    }
+    fn from_f64(n: f64) -> Self {
+        n as Self
+    }
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl Numeric for rust_decimal::Decimal {
+    const ZERO: Self = rust_decimal::Decimal::ZERO;
+    const ONE: Self = rust_decimal::Decimal::ONE;
+
+    fn two() -> Self {
+        rust_decimal::Decimal::TWO
+    }
+    fn fifty() -> Self {
+        rust_decimal::Decimal::from(50)
+    }
+    fn hundred() -> Self {
+        rust_decimal::Decimal::ONE_HUNDRED
+    }
+    fn abs(self) -> Self {
+        rust_decimal::Decimal::abs(&self)
+    }
+    fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+    fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+    fn signum(self) -> Self {
+        if self.is_zero() {
+            Self::ZERO
+        } else if self.is_sign_negative() {
+            rust_decimal::Decimal::NEGATIVE_ONE
+        } else {
+            Self::ONE
+        }
+    }
+    fn sqrt(self) -> Self {
+        rust_decimal::MathematicalOps::sqrt(&self).expect("square root of negative decimal")
+    }
+    fn ln(self) -> Self {
+        rust_decimal::MathematicalOps::checked_ln(&self).expect("logarithm of non-positive decimal")
+    }
+    fn exp(self) -> Self {
+        rust_decimal::MathematicalOps::checked_exp(&self).expect("decimal overflow")
+    }
+    fn powi(self, n: i32) -> Self {
+        rust_decimal::MathematicalOps::checked_powi(&self, n.into()).expect("decimal overflow")
+    }
+    fn powf(self, n: Self) -> Self {
+        rust_decimal::MathematicalOps::checked_powd(&self, n).expect("decimal overflow")
+    }
+    fn is_finite(self) -> bool {
+        true
+    }
+    fn from_usize(n: usize) -> Self {
+        rust_decimal::Decimal::from(n)
+    }
+    fn from_f64(n: f64) -> Self {
+        <rust_decimal::Decimal as rust_decimal::prelude::FromPrimitive>::from_f64(n)
+            .expect("f64 value out of decimal range")
+    }
+    fn to_f64(self) -> f64 {
+        rust_decimal::prelude::ToPrimitive::to_f64(&self).expect("decimal out of f64 range")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_math<T: Numeric>() {
+        let two = T::two();
+        let half = T::ONE / two;
+        let close = |a: T, b: f64| (a.to_f64() - b).abs() < 1e-5;
+
+        assert_eq!(two.min(T::ONE), T::ONE);
+        assert_eq!((T::ZERO - two).signum(), T::ZERO - T::ONE);
+        assert_eq!(two.signum(), T::ONE);
+        assert_eq!(T::ZERO.signum(), T::ZERO);
+        assert_eq!(two.powi(3), T::from_usize(8));
+        assert_eq!(two.powi(-1), half);
+        assert!(close(two.powf(half), std::f64::consts::SQRT_2));
+        assert!(close(T::ONE.exp(), std::f64::consts::E));
+        assert!(close(T::from_f64(std::f64::consts::E).ln(), 1.0));
+        assert!(close(T::hundred().ln(), 100f64.ln()));
+        assert!(T::fifty().is_finite());
+    }
+
+    #[test]
+    fn test_math() {
+        check_math::<f32>();
+        check_math::<f64>();
+        #[cfg(feature = "decimal")]
+        check_math::<rust_decimal::Decimal>();
+        check_math::<crate::core::Fixed<8>>();
+
+        assert!(!(1.0f64 / 0.0).is_finite());
+        assert_eq!(Numeric::signum(-0.0f64), 0.0);
+        assert!(Numeric::signum(f64::NAN).is_nan());
+        assert!(Numeric::ln(-1.0f64).is_nan());
+    }
 }
@@ -0,0 +1,259 @@
+//! Zero-copy views over candle series.
+
+use std::ops::Range;
+
+use super::{Candle, CandleRef, CandleSeries, Column, Numeric, Timeframe, gains_losses};
+
+/// Borrowed view over a contiguous range of candles in a [`CandleSeries`].
+///
+/// Views are cheap to copy and can be passed to every indicator function in
+/// place of a full series.
+#[derive(Debug, Clone, Copy)]
+pub struct CandleSeriesView<'a, T> {
+    opens: &'a [T],
+    highs: &'a [T],
+    lows: &'a [T],
+    closes: &'a [T],
+    volumes: &'a [T],
+    timestamps: &'a [u64],
+    timeframe: Timeframe,
+    max_len: Option<usize>,
+}
+
+impl<'a, T: Numeric> CandleSeriesView<'a, T> {
+    /// Returns the opening prices in this view.
+    pub fn opens(&self) -> &'a [T] {
+        self.opens
+    }
+
+    /// Returns the high prices in this view.
+    pub fn highs(&self) -> &'a [T] {
+        self.highs
+    }
+
+    /// Returns the low prices in this view.
+    pub fn lows(&self) -> &'a [T] {
+        self.lows
+    }
+
+    /// Returns the closing prices in this view.
+    pub fn closes(&self) -> &'a [T] {
+        self.closes
+    }
+
+    /// Returns the volumes in this view.
+    pub fn volumes(&self) -> &'a [T] {
+        self.volumes
+    }
+
+    /// Returns the start timestamp of each candle in this view.
+    pub fn timestamps(&self) -> &'a [u64] {
+        self.timestamps
+    }
+
+    /// Returns the timeframe of each candle.
+    pub fn timeframe(&self) -> Timeframe {
+        self.timeframe
+    }
+
+    /// Returns the maximum number of candles retained by the series this view
+    /// borrows from, or None if it is unbounded.
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Returns the number of candles in the view.
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    /// Returns true if the view contains no candles.
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    /// Gets a candle at the specified index as a reference.
+    pub fn get(&self, index: usize) -> Option<CandleRef<'a, T>> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(CandleRef {
+            open: &self.opens[index],
+            high: &self.highs[index],
+            low: &self.lows[index],
+            close: &self.closes[index],
+            volume: &self.volumes[index],
+        })
+    }
+
+    /// Gets a candle at the specified index as an owned value.
+    pub fn get_owned(&self, index: usize) -> Option<Candle<T>> {
+        self.get(index).map(Candle::from)
+    }
+
+    /// Returns the index of the candle starting exactly at `ts`.
+    ///
+    /// Tick, volume and dollar bars can share a start timestamp; in that case
+    /// the first of them is returned.
+    pub fn index_of(&self, ts: u64) -> Option<usize> {
+        let index = self.timestamps.partition_point(|&start| start < ts);
+        (self.timestamps.get(index) == Some(&ts)).then_some(index)
+    }
+
+    /// Gets the candle whose period contains `ts`, using binary search.
+    ///
+    /// If several candles share a start timestamp, the last of them is returned.
+    pub fn get_at(&self, ts: u64) -> Option<CandleRef<'a, T>> {
+        let index = self.timestamps.partition_point(|&start| start <= ts);
+        if index == 0 || ts >= self.timeframe.next_start(self.timestamps[index - 1]) {
+            return None;
+        }
+        self.get(index - 1)
+    }
+
+    /// Returns a view of the candles starting within `range`.
+    ///
+    /// Candles sharing a start timestamp are either all included or all excluded.
+    pub fn slice(&self, range: Range<u64>) -> CandleSeriesView<'a, T> {
+        let start = self.timestamps.partition_point(|&ts| ts < range.start);
+        let end = self
+            .timestamps
+            .partition_point(|&ts| ts < range.end)
+            .max(start);
+        self.slice_index(start..end)
+    }
+
+    /// Returns a view of the candles within the index `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds.
+    pub fn slice_index(&self, range: Range<usize>) -> CandleSeriesView<'a, T> {
+        CandleSeriesView {
+            opens: &self.opens[range.clone()],
+            highs: &self.highs[range.clone()],
+            lows: &self.lows[range.clone()],
+            closes: &self.closes[range.clone()],
+            volumes: &self.volumes[range.clone()],
+            timestamps: &self.timestamps[range],
+            timeframe: self.timeframe,
+            max_len: self.max_len,
+        }
+    }
+
+    /// Calculates gains and losses between consecutive closing prices.
+    /// Returns a tuple of (gains, losses) columns.
+    pub fn gains_losses(&self, max_history: Option<usize>) -> (Column<T>, Column<T>) {
+        gains_losses(self.closes, max_history)
+    }
+
+    /// Calculate the true range for each candle in this view.
+    ///
+    /// See [`CandleSeries::true_range`].
+    pub fn true_range(&self, max_history: Option<usize>) -> Column<T> {
+        if self.is_empty() {
+            return Column::new();
+        }
+
+        let len = self.len();
+        let start = match max_history {
+            Some(max) => len.saturating_sub(max),
+            None => 0,
+        };
+
+        let mut tr = Column::with_capacity(len - start);
+        for i in start..len {
+            let candle = self.get(i).unwrap();
+            if i == 0 {
+                let simple_range = *candle.high - *candle.low;
+                tr.push(simple_range);
+            } else {
+                let prev_candle = self.get(i - 1).unwrap();
+                let range = candle.true_range(&prev_candle);
+                tr.push(range);
+            }
+        }
+        tr
+    }
+}
+
+impl<'a, T: Numeric> From<&'a CandleSeries<T>> for CandleSeriesView<'a, T> {
+    fn from(candles: &'a CandleSeries<T>) -> Self {
+        CandleSeriesView {
+            opens: candles.opens().as_slice(),
+            highs: candles.highs().as_slice(),
+            lows: candles.lows().as_slice(),
+            closes: candles.closes().as_slice(),
+            volumes: candles.volumes().as_slice(),
+            timestamps: candles.timestamps().as_slice(),
+            timeframe: candles.timeframe(),
+            max_len: candles.max_len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_data() -> CandleSeries<f64> {
+        let mut candles = CandleSeries::new(60);
+        for i in 0..10u64 {
+            candles.push(i as f64, 1.0, i * 60 + 5).unwrap();
+        }
+        candles
+    }
+
+    #[test]
+    fn test_time_lookup() {
+        let candles = get_test_data();
+
+        assert_eq!(candles.index_of(120), Some(2));
+        assert_eq!(candles.index_of(125), None);
+        assert_eq!(*candles.get_at(179).unwrap().close, 2.0);
+        assert_eq!(*candles.get_at(180).unwrap().close, 3.0);
+        assert!(candles.get_at(600).is_none());
+    }
+
+    #[test]
+    fn test_slice() {
+        let candles = get_test_data();
+
+        let view = candles.slice(120..300);
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.timestamps(), &[120, 180, 240]);
+        assert_eq!(view.closes(), &[2.0, 3.0, 4.0]);
+        assert_eq!(view.index_of(180), Some(1));
+
+        let nested = view.slice(170..1000);
+        assert_eq!(nested.timestamps(), &[180, 240]);
+        assert!(candles.slice(300..300).is_empty());
+
+        // The first candle in a view has no previous candle
+        assert_eq!(view.true_range(None)[0], 0.0);
+        let (gains, _) = view.gains_losses(None);
+        assert_eq!(gains.as_slice(), &[0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_duplicate_timestamps() {
+        // Activity-driven bars may close several times within the same millisecond
+        let mut candles = CandleSeries::new(60);
+        for (i, ts) in [0, 60, 60, 60, 120].into_iter().enumerate() {
+            let price = i as f64;
+            let candle = Candle {
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: 1.0,
+            };
+            candles.push_candle_unchecked(candle, ts);
+        }
+
+        assert_eq!(candles.index_of(60), Some(1));
+        assert_eq!(candles.index_of(120), Some(4));
+        assert_eq!(*candles.get_at(90).unwrap().close, 3.0);
+        assert_eq!(candles.slice(60..120).closes(), &[1.0, 2.0, 3.0]);
+        assert_eq!(candles.slice(0..61).len(), 4);
+    }
+}
@@ -1,146 +1,549 @@
 //! Configuration structures for technical indicators.
 
-use crate::core::Numeric;
+use crate::core::{Error, EwmMode, Numeric};
+
+/// Moving average used by an indicator to smooth its intermediate series,
+/// such as RSI's average gain and loss or NATR's true range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Smoothing {
+    /// Simple moving average over `period` values (e.g. Cutler's RSI).
+    Sma,
+    /// Exponential moving average with the configured alpha, `2 / (period + 1)` by default.
+    ///
+    /// Exponential smoothers are weighted according to [`Config::ewm_mode`]. The
+    /// default recursive mode is seeded with the first input, unlike
+    /// [`MovingAverage::Ema`](crate::indicators::MovingAverage::Ema), which is seeded
+    /// with a simple mean like [`EwmMode::SmaSeeded`].
+    #[default]
+    Ema,
+    /// Wilder's smoothing, also known as RMA: an exponential moving average with
+    /// `alpha = 1 / period`. Wilder seeded it with a simple mean, which
+    /// [`EwmMode::SmaSeeded`] reproduces; see [`Smoothing::Ema`] for the default.
+    Wilder,
+    /// Linearly weighted moving average over `period` values.
+    Wma,
+    /// Double exponential moving average, `2 * EMA - EMA(EMA)`.
+    Dema,
+    /// Triple exponential moving average, `3 * EMA - 3 * EMA(EMA) + EMA(EMA(EMA))`.
+    Tema,
+}
+
+impl Smoothing {
+    /// Returns the number of inputs required before the smoothed value is valid.
+    pub fn warmup(&self, period: usize) -> usize {
+        let period = period.max(1);
+        match self {
+            Smoothing::Sma | Smoothing::Ema | Smoothing::Wilder | Smoothing::Wma => period,
+            Smoothing::Dema => 2 * period - 1,
+            Smoothing::Tema => 3 * period - 2,
+        }
+    }
+
+    /// Returns true for the exponential smoothers, which use alpha and an [`EwmMode`].
+    pub fn is_exponential(&self) -> bool {
+        matches!(
+            self,
+            Smoothing::Ema | Smoothing::Wilder | Smoothing::Dema | Smoothing::Tema
+        )
+    }
+
+    /// Returns the conventional alpha for exponential smoothers over `period` values,
+    /// or None for smoothers that do not use alpha.
+    pub fn alpha<T: Numeric>(&self, period: usize) -> Option<T> {
+        let period = T::from_usize(period.max(1));
+        match self {
+            Smoothing::Ema | Smoothing::Dema | Smoothing::Tema => {
+                Some(T::two() / (period + T::ONE))
+            }
+            Smoothing::Wilder => Some(T::ONE / period),
+            Smoothing::Sma | Smoothing::Wma => None,
+        }
+    }
+}
 
 /// Configuration for technical indicators.
 ///
-/// This struct provides common parameters for indicators that use exponential
-/// moving averages and require historical data management.
+/// This struct provides common parameters for indicators that smooth an
+/// intermediate series and require historical data management.
 ///
 /// # Fields
-/// * `alpha` - Smoothing factor for exponential moving average (0 < alpha < 1)
+/// * `alpha` - Smoothing factor for exponential smoothers (0 < alpha <= 1)
 /// * `period` - Number of periods for calculation (e.g., 14 for RSI-14)
 /// * `max_history` - Maximum data points to retain for efficiency
+/// * `smoothing` - Moving average applied to the intermediate series
+/// * `ewm_mode` - Weighting and seeding of exponential smoothers, recursive by default
+/// * `min_periods` - Minimum number of smoothed inputs before a value is valid
 ///
 /// # Alpha Calculation
-/// * **EMA**: `alpha = 2.0 / (period + 1.0)` - Standard exponential moving average
-/// * **Wilder**: `alpha = 1.0 / period` - Wilder's smoothing (used in RSI)
+/// * **EMA**: `alpha = 2 / (period + 1)` - Standard exponential moving average, see [`Config::ema`]
+/// * **Wilder**: `alpha = 1 / period` - Wilder's smoothing (used in RSI), see [`Config::wilder`]
+///
+/// # EWM Mode
+/// * **Recursive**: seeded with the first input, the default
+/// * **Adjusted**: pandas' `adjust=True`, as used by pandas-ta
+/// * **SmaSeeded**: seeded with the simple mean of the first inputs, as TA-Lib does
+///
+/// Adjusted and SMA-seeded smoothers start from the first change measured against
+/// a previous candle, whereas recursive ones are seeded with the zero change (or
+/// high-low range) of a series' first candle.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::{
+///     core::EwmMode,
+///     indicators::{Config, Smoothing},
+/// };
+///
+/// // Cutler's RSI averages gains and losses with a simple moving average
+/// let cutler = Config::<f64>::ema(14, 100).with_smoothing(Smoothing::Sma);
+/// assert_eq!(cutler.smoothing, Smoothing::Sma);
+///
+/// // TA-Lib's RSI seeds Wilder's smoothing with the mean of the first 14 changes
+/// let talib = Config::<f64>::wilder(14, 100).with_ewm_mode(EwmMode::SmaSeeded(14));
+/// assert_eq!(talib.warmup(), 14);
+/// ```
 #[derive(Debug, Clone)]
 pub struct Config<T> {
     pub alpha: T,
     pub period: usize,
     pub max_history: usize,
+    pub smoothing: Smoothing,
+    pub ewm_mode: EwmMode,
+    pub min_periods: usize,
 }
 
 impl<T: Numeric> Config<T> {
-    /// Creates a new configuration with custom alpha value.
+    /// Creates a new configuration with custom alpha value and EMA smoothing.
     ///
     /// # Panics
-    /// Panics if `max_history < period`.
+    /// Panics if the configuration is invalid; see [`Config::try_new`].
     pub fn new(alpha: T, period: usize, max_history: usize) -> Self {
-        if max_history < period {
-            panic!("max_history must be greater than or equal to period");
-        }
+        Self::try_new(alpha, period, max_history).unwrap_or_else(|err| panic!("{err}"))
+    }
 
-        Self {
+    /// Creates a new configuration with custom alpha value and EMA smoothing,
+    /// validating its parameters.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if `period` is zero, `alpha` is not within
+    /// (0, 1] or `max_history` is shorter than the warmup.
+    pub fn try_new(alpha: T, period: usize, max_history: usize) -> Result<Self, Error> {
+        let config = Self {
             alpha,
             period,
             max_history,
+            smoothing: Smoothing::Ema,
+            ewm_mode: EwmMode::Recursive,
+            min_periods: 0,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the configuration can drive an indicator.
+    ///
+    /// Fields are public, so every indicator entry point revalidates its
+    /// configuration before use.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if `period` is zero, `alpha` is not within
+    /// (0, 1], the SMA seed window of `ewm_mode` is empty for an exponential
+    /// smoother or `max_history` is shorter than the [warmup](Config::warmup), which
+    /// would never produce a value.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.period == 0 {
+            return Err(Error::InvalidConfig(
+                "period must be greater than zero".to_string(),
+            ));
         }
+        // Written so that NaN fails the check
+        if !(self.alpha > T::ZERO && self.alpha <= T::ONE) {
+            return Err(Error::InvalidConfig(format!(
+                "alpha must be within (0, 1], got {:?}",
+                self.alpha
+            )));
+        }
+        // Only exponential smoothers read the weighting mode
+        if self.smoothing.is_exponential() && self.ewm_mode == EwmMode::SmaSeeded(0) {
+            return Err(Error::InvalidConfig(
+                "SMA seed window must be greater than zero".to_string(),
+            ));
+        }
+        if self.max_history < self.warmup() {
+            return Err(Error::InvalidConfig(format!(
+                "max_history ({}) must be greater than or equal to warmup ({})",
+                self.max_history,
+                self.warmup()
+            )));
+        }
+        Ok(())
     }
-}
 
-impl Config<f64> {
-    /// Creates a configuration with standard EMA smoothing for f64.
+    /// Returns the number of inputs required before the smoothed value is valid,
+    /// including the SMA seed window of exponential smoothers and `min_periods`.
+    pub fn warmup(&self) -> usize {
+        let period = match self.ewm_mode {
+            EwmMode::SmaSeeded(seed) if self.smoothing.is_exponential() => self.period.max(seed),
+            _ => self.period,
+        };
+        self.smoothing.warmup(period).max(self.min_periods)
+    }
+
+    /// Creates a configuration with standard EMA smoothing.
     ///
-    /// Uses alpha = 2.0 / (period + 1.0) for standard exponential moving average.
+    /// Uses alpha = 2 / (period + 1) for standard exponential moving average.
     ///
     /// # Arguments
     /// * `period` - Number of periods (e.g., 14 for RSI-14)
     /// * `max_history` - Maximum data points to retain
-    pub fn new_f64(period: usize, max_history: usize) -> Self {
-        if max_history < period {
-            panic!("max_history must be greater than or equal to period");
-        }
-
-        let alpha = 2.0 / (period as f64 + 1.0);
-        Self {
-            alpha,
-            period,
-            max_history,
-        }
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`Config::try_new`].
+    pub fn ema(period: usize, max_history: usize) -> Self {
+        Self::with_default_alpha(Smoothing::Ema, period, max_history)
     }
 
-    /// Creates a configuration with Wilder's smoothing for f64.
+    /// Creates a configuration with Wilder's smoothing.
     ///
-    /// Uses alpha = 1.0 / period for Wilder's smoothing method,
+    /// Uses alpha = 1 / period for Wilder's smoothing method,
     /// commonly used in RSI and related momentum indicators.
     ///
     /// # Arguments
     /// * `period` - Number of periods (e.g., 14 for RSI-14)
     /// * `max_history` - Maximum data points to retain
-    pub fn new_f64_wilder(period: usize, max_history: usize) -> Self {
-        if max_history < period {
-            panic!("max_history must be greater than or equal to period");
-        }
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`Config::try_new`].
+    pub fn wilder(period: usize, max_history: usize) -> Self {
+        Self::with_default_alpha(Smoothing::Wilder, period, max_history)
+    }
 
-        let alpha = 1.0 / period as f64;
-        Self {
-            alpha,
-            period,
-            max_history,
+    /// Switches to another smoother, setting alpha to its conventional value
+    /// for exponential smoothers.
+    pub fn with_smoothing(mut self, smoothing: Smoothing) -> Self {
+        if let Some(alpha) = smoothing.alpha(self.period) {
+            self.alpha = alpha;
         }
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Switches exponential smoothers to another weighting mode.
+    pub fn with_ewm_mode(mut self, ewm_mode: EwmMode) -> Self {
+        self.ewm_mode = ewm_mode;
+        self
+    }
+
+    /// Masks smoothed values until at least `min_periods` inputs have been seen,
+    /// like pandas' `min_periods`.
+    pub fn with_min_periods(mut self, min_periods: usize) -> Self {
+        self.min_periods = min_periods;
+        self
+    }
+
+    fn with_default_alpha(smoothing: Smoothing, period: usize, max_history: usize) -> Self {
+        let alpha = smoothing.alpha(period).unwrap_or(T::ONE);
+        let mut config = Self::new(alpha, period, max_history);
+        config.smoothing = smoothing;
+        config
+    }
+}
+
+impl Config<f64> {
+    /// Creates a configuration with standard EMA smoothing for f64.
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`Config::try_new`].
+    #[deprecated(since = "0.1.0", note = "use `Config::ema` instead")]
+    pub fn new_f64(period: usize, max_history: usize) -> Self {
+        Self::ema(period, max_history)
+    }
+
+    /// Creates a configuration with Wilder's smoothing for f64.
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`Config::try_new`].
+    #[deprecated(since = "0.1.0", note = "use `Config::wilder` instead")]
+    pub fn new_f64_wilder(period: usize, max_history: usize) -> Self {
+        Self::wilder(period, max_history)
     }
 }
 
 impl Config<f32> {
     /// Creates a configuration with standard EMA smoothing for f32.
     ///
-    /// Uses alpha = 2.0 / (period + 1.0) for standard exponential moving average.
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`Config::try_new`].
+    #[deprecated(since = "0.1.0", note = "use `Config::ema` instead")]
     pub fn new_f32(period: usize, max_history: usize) -> Self {
-        if max_history < period {
-            panic!("max_history must be greater than or equal to period");
-        }
-
-        let alpha = 2.0 / (period as f32 + 1.0);
-        Self {
-            alpha,
-            period,
-            max_history,
-        }
+        Self::ema(period, max_history)
     }
 
     /// Creates a configuration with Wilder's smoothing for f32.
     ///
-    /// Uses alpha = 1.0 / period for Wilder's smoothing method.
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`Config::try_new`].
+    #[deprecated(since = "0.1.0", note = "use `Config::wilder` instead")]
     pub fn new_f32_wilder(period: usize, max_history: usize) -> Self {
-        if max_history < period {
-            panic!("max_history must be greater than or equal to period");
-        }
-
-        let alpha = 1.0 / period as f32;
-        Self {
-            alpha,
-            period,
-            max_history,
-        }
+        Self::wilder(period, max_history)
     }
 }
 
-impl Default for Config<f64> {
+impl<T: Numeric> Default for Config<T> {
     /// Default configuration: 14-period with EMA smoothing and 140 max history.
     fn default() -> Self {
-        let period = 14;
-        let max_history = period * 10;
-        let alpha = 2.0 / (period as f64 + 1.0);
-        Self {
-            alpha,
-            period,
+        Self::ema(14, 140)
+    }
+}
+
+/// Configuration for MACD.
+///
+/// # Fields
+/// * `fast` - Period of the fast moving average (e.g., 12)
+/// * `slow` - Period of the slow moving average (e.g., 26)
+/// * `signal` - Period of the signal line's moving average (e.g., 9)
+/// * `max_history` - Maximum data points to retain for efficiency
+/// * `smoothing` - Moving average of the fast and slow lines
+/// * `signal_smoothing` - Moving average of the signal line
+///
+/// Exponential smoothers use their conventional alpha for each period.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::indicators::{MacdConfig, Smoothing};
+///
+/// let config = MacdConfig::new(12, 26, 9, 260).with_smoothing(Smoothing::Sma);
+/// assert_eq!(config.signal_smoothing, Smoothing::Ema);
+/// assert_eq!(config.warmup(), 34);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacdConfig {
+    pub fast: usize,
+    pub slow: usize,
+    pub signal: usize,
+    pub max_history: usize,
+    pub smoothing: Smoothing,
+    pub signal_smoothing: Smoothing,
+}
+
+impl MacdConfig {
+    /// Creates a new MACD configuration with EMA smoothing for every line.
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`MacdConfig::try_new`].
+    pub fn new(fast: usize, slow: usize, signal: usize, max_history: usize) -> Self {
+        Self::try_new(fast, slow, signal, max_history).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates a new MACD configuration with EMA smoothing for every line,
+    /// validating its parameters.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if a period is zero, `fast >= slow` or
+    /// `max_history` is shorter than the [warmup](MacdConfig::warmup).
+    pub fn try_new(
+        fast: usize,
+        slow: usize,
+        signal: usize,
+        max_history: usize,
+    ) -> Result<Self, Error> {
+        let config = Self {
+            fast,
+            slow,
+            signal,
             max_history,
+            smoothing: Smoothing::Ema,
+            signal_smoothing: Smoothing::Ema,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the configuration can drive MACD.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if a period is zero, `fast >= slow` or
+    /// `max_history` is shorter than the [warmup](MacdConfig::warmup).
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.fast == 0 || self.slow == 0 || self.signal == 0 {
+            return Err(Error::InvalidConfig(
+                "periods must be greater than zero".to_string(),
+            ));
         }
+        if self.fast >= self.slow {
+            return Err(Error::InvalidConfig(format!(
+                "fast period ({}) must be less than slow period ({})",
+                self.fast, self.slow
+            )));
+        }
+        if self.max_history < self.warmup() {
+            return Err(Error::InvalidConfig(format!(
+                "max_history ({}) must be greater than or equal to warmup ({})",
+                self.max_history,
+                self.warmup()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Switches the fast and slow lines to another smoother.
+    pub fn with_smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Switches the signal line to another smoother.
+    pub fn with_signal_smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.signal_smoothing = smoothing;
+        self
+    }
+
+    /// Returns the number of candles required before the MACD line is valid.
+    pub fn line_warmup(&self) -> usize {
+        self.smoothing
+            .warmup(self.fast)
+            .max(self.smoothing.warmup(self.slow))
+    }
+
+    /// Returns the number of candles required before the signal line and
+    /// histogram are valid.
+    pub fn warmup(&self) -> usize {
+        self.line_warmup() + self.signal_smoothing.warmup(self.signal) - 1
     }
 }
 
-impl Default for Config<f32> {
-    /// Default configuration: 14-period with EMA smoothing and 140 max history.
+impl Default for MacdConfig {
+    /// Default configuration: the classic 12/26/9 EMA MACD with 260 max history.
     fn default() -> Self {
-        let period = 14;
-        let max_history = period * 10;
-        let alpha = 2.0 / (period as f32 + 1.0);
-        Self {
-            alpha,
-            period,
-            max_history,
+        Self::new(12, 26, 9, 260)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Fixed;
+
+    #[test]
+    fn test_generic_constructors() {
+        assert_eq!(Config::<f64>::ema(14, 100).alpha, 2.0 / 15.0);
+        assert_eq!(Config::<f32>::wilder(14, 100).alpha, 1.0 / 14.0);
+        assert_eq!(Config::<f32>::wilder(14, 100).smoothing, Smoothing::Wilder);
+
+        let fixed = Config::<Fixed<4>>::ema(3, 10);
+        assert_eq!(fixed.alpha, Fixed::from_raw(5_000));
+        assert_eq!(fixed.smoothing, Smoothing::Ema);
+
+        let default = Config::<f64>::default();
+        assert_eq!((default.period, default.max_history), (14, 140));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_constructors() {
+        assert_eq!(
+            Config::new_f64(14, 100).alpha,
+            Config::<f64>::ema(14, 100).alpha
+        );
+        assert_eq!(Config::new_f64_wilder(14, 100).smoothing, Smoothing::Wilder);
+        assert_eq!(
+            Config::new_f32(14, 100).alpha,
+            Config::<f32>::ema(14, 100).alpha
+        );
+        assert_eq!(Config::new_f32_wilder(14, 100).alpha, 1.0 / 14.0);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(Config::try_new(0.5, 14, 100).is_ok());
+        for (alpha, period, max_history) in [
+            (0.5, 0, 100),
+            (0.0, 14, 100),
+            (1.5, 14, 100),
+            (f64::NAN, 14, 100),
+            (0.5, 14, 10),
+        ] {
+            let result = Config::try_new(alpha, period, max_history);
+            assert!(matches!(result, Err(Error::InvalidConfig(_))));
+        }
+
+        let err = Config::try_new(0.5, 14, 10).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid config: max_history (10) must be greater than or equal to warmup (14)"
+        );
+
+        // Configs mutated after construction are rejected by indicator entry points
+        let mut config = Config::wilder(14, 100);
+        config.alpha = 2.0;
+        let candles = crate::core::CandleSeries::<f64>::new(60);
+        assert!(matches!(
+            crate::indicators::rsi_series(&candles, &config),
+            Err(Error::InvalidConfig(_))
+        ));
+
+        let config = Config::<f64>::wilder(14, 100).with_ewm_mode(EwmMode::SmaSeeded(0));
+        assert!(matches!(config.validate(), Err(Error::InvalidConfig(_))));
+
+        // Window smoothers ignore the weighting mode
+        for smoothing in [Smoothing::Sma, Smoothing::Wma] {
+            assert!(config.clone().with_smoothing(smoothing).validate().is_ok());
+        }
+
+        // A window shorter than the warmup would never produce a value
+        for config in [
+            Config::<f64>::ema(10, 10).with_smoothing(Smoothing::Dema),
+            Config::wilder(14, 20).with_min_periods(50),
+            Config::wilder(14, 20).with_ewm_mode(EwmMode::SmaSeeded(30)),
+        ] {
+            assert!(matches!(config.validate(), Err(Error::InvalidConfig(_))));
         }
+        assert!(
+            Config::<f64>::ema(10, 19)
+                .with_smoothing(Smoothing::Dema)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_config_warmup() {
+        let config = Config::<f64>::wilder(14, 100);
+        assert_eq!(config.warmup(), 14);
+        assert_eq!(config.clone().with_min_periods(20).warmup(), 20);
+        assert_eq!(config.clone().with_min_periods(5).warmup(), 14);
+
+        // A longer SMA seed window delays exponential smoothers only
+        let seeded = config.with_ewm_mode(EwmMode::SmaSeeded(20));
+        assert_eq!(seeded.warmup(), 20);
+        assert_eq!(seeded.clone().with_smoothing(Smoothing::Dema).warmup(), 39);
+        assert_eq!(seeded.with_smoothing(Smoothing::Sma).warmup(), 14);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be greater than zero")]
+    fn test_zero_period_panics() {
+        let _ = Config::<f64>::ema(0, 100);
+    }
+
+    #[test]
+    fn test_macd_config_validation() {
+        assert!(MacdConfig::try_new(12, 26, 9, 260).is_ok());
+        assert!(MacdConfig::try_new(0, 26, 9, 260).is_err());
+        assert!(MacdConfig::try_new(12, 26, 0, 260).is_err());
+        assert!(MacdConfig::try_new(26, 12, 9, 260).is_err());
+        assert!(MacdConfig::try_new(12, 26, 9, 20).is_err());
+        assert!(MacdConfig::try_new(12, 26, 9, 33).is_err());
+        assert!(MacdConfig::try_new(12, 26, 9, 34).is_ok());
+        let dema = MacdConfig {
+            max_history: 50,
+            ..MacdConfig::default()
+        }
+        .with_smoothing(Smoothing::Dema);
+        assert!(dema.validate().is_err());
+
+        let config = MacdConfig::default().with_smoothing(Smoothing::Dema);
+        assert_eq!(config.line_warmup(), 51);
+        assert_eq!(config.warmup(), 59);
     }
 }
@@ -0,0 +1,198 @@
+//! Common interface shared by all technical indicators.
+
+use crate::{
+    core::{Aligned, Candle, CandleSeriesView, Error, EwmMode, MaskedColumn, Numeric},
+    indicators::Config,
+};
+
+/// Common interface for technical indicators.
+///
+/// An indicator can be computed over a full candle series or driven incrementally,
+/// one input at a time. `I` is the input consumed per update and `O` the value
+/// produced; both default to candles and the series' numeric type so that different
+/// indicators can be held together as `Box<dyn Indicator<T>>`.
+///
+/// Values produced before the indicator has seen [`Indicator::warmup`] candles have
+/// not converged; they are masked in computed series and returned as None by updates.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::{
+///     core::{Candle, CandleSeries},
+///     indicators::{Config, Indicator, NatrState, RsiState},
+/// };
+///
+/// let config = Config::wilder(14, 100);
+/// let mut indicators: Vec<Box<dyn Indicator<f64>>> = vec![
+///     Box::new(RsiState::new(&config)),
+///     Box::new(NatrState::new(&config)),
+/// ];
+///
+/// let candle = Candle { open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: 10.0 };
+/// for indicator in indicators.iter_mut() {
+///     indicator.update(candle);
+/// }
+/// ```
+pub trait Indicator<T: Numeric, I = Candle<T>, O = T> {
+    /// Returns the number of candles required before the indicator produces a valid value.
+    fn warmup(&self) -> usize;
+
+    /// Computes the indicator over a full candle series without touching the current state.
+    /// Values within the warmup are masked, and the output is aligned with the last
+    /// `max_history` candles it was computed from.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid, or
+    /// `Error::NotEnoughData` if the series is shorter than the warmup length.
+    fn compute_series(
+        &self,
+        candles: CandleSeriesView<'_, T>,
+    ) -> Result<Aligned<MaskedColumn<O>>, Error>;
+
+    /// Computes the latest value over a full candle series without touching the
+    /// current state. The value equals the last one of [`Indicator::compute_series`];
+    /// indicators override this to fold the window into a fresh state instead of
+    /// building the whole series.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid, or
+    /// `Error::NotEnoughData` if the latest value is within the warmup.
+    fn latest(&self, candles: CandleSeriesView<'_, T>) -> Result<O, Error>
+    where
+        O: Copy,
+    {
+        self.compute_series(candles)?
+            .last()
+            .ok_or(Error::NotEnoughData)
+    }
+
+    /// Replaces the current state with one seeded from a candle series.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid, or
+    /// `Error::NotEnoughData` if the series is shorter than the warmup length.
+    fn seed(&mut self, candles: CandleSeriesView<'_, T>) -> Result<(), Error>;
+
+    /// Folds the next completed input into the state and returns the new value,
+    /// or None while the indicator is warming up.
+    fn update(&mut self, input: I) -> Option<O>;
+
+    /// Clears all accumulated state, keeping the configuration.
+    fn reset(&mut self);
+}
+
+/// Returns `Error::NotEnoughData` if `len` is shorter than the required warmup.
+pub(crate) fn ensure_warmup(len: usize, warmup: usize) -> Result<(), Error> {
+    if len < warmup {
+        return Err(Error::NotEnoughData);
+    }
+    Ok(())
+}
+
+/// Returns the index of the first candle in the window of the last `max_history` candles.
+pub(crate) fn window_start<T>(len: usize, config: &Config<T>) -> usize {
+    len.saturating_sub(config.max_history)
+}
+
+/// Tags the output of a windowed computation over `candles` with the first
+/// candle of its window.
+pub(crate) fn align<T: Numeric, C>(
+    candles: &CandleSeriesView<'_, T>,
+    output: C,
+    config: &Config<T>,
+) -> Aligned<C> {
+    let start = window_start(candles.len(), config);
+    let start_ts = candles.timestamps().get(start).copied().unwrap_or_default();
+    Aligned::new(output, start, start_ts)
+}
+
+/// Returns the number of leading values of a windowed computation that are based on
+/// fewer observed changes than the smoothing warmup.
+///
+/// The first candle of a series has no predecessor, so its change does not count;
+/// a window truncated by `max_history` measures its first change against the candle
+/// before it.
+pub(crate) fn unconverged_len<T: Numeric>(len: usize, config: &Config<T>) -> usize {
+    let warmup = config.warmup();
+    if len > config.max_history {
+        warmup - 1
+    } else {
+        warmup
+    }
+}
+
+/// Returns true if the smoother is seeded with the change of a candle that has no
+/// previous candle, which is zero for RSI and the high-low range for NATR.
+///
+/// Only recursive exponential smoothers and window smoothers take it; adjusted and
+/// SMA-seeded exponential smoothers start from the first measured change, as
+/// pandas-ta and TA-Lib do.
+pub(crate) fn seeds_with_first_candle<T>(config: &Config<T>) -> bool {
+    !config.smoothing.is_exponential() || config.ewm_mode == EwmMode::Recursive
+}
+
+/// Returns the number of leading changes of a windowed computation that are not
+/// fed to the smoother, see [`seeds_with_first_candle`].
+pub(crate) fn skipped_changes<T: Numeric>(len: usize, config: &Config<T>) -> usize {
+    usize::from(len <= config.max_history && !seeds_with_first_candle(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        indicators::{Config, NatrState, RsiState},
+        test_utils::{CLOSES, candles_from_closes},
+    };
+
+    #[test]
+    fn test_drive_heterogeneous_indicators() {
+        let candles = candles_from_closes(&CLOSES[..20]);
+        let config = Config::wilder(14, 100);
+
+        let mut indicators: Vec<Box<dyn Indicator<f64>>> = vec![
+            Box::new(RsiState::new(&config)),
+            Box::new(NatrState::new(&config)),
+        ];
+
+        for indicator in indicators.iter_mut() {
+            assert_eq!(indicator.warmup(), 15);
+
+            let series = indicator.compute_series(candles.view()).unwrap();
+            assert_eq!(series.first_valid(), Some(indicator.warmup() - 1));
+            assert_eq!(indicator.latest(candles.view()).ok(), series.last());
+            for i in 0..candles.len() {
+                let value = indicator.update(candles.get_owned(i).unwrap());
+                assert_eq!(value, series.get(i));
+            }
+
+            indicator.reset();
+            indicator.seed(candles.view()).unwrap();
+            let next = candles.get_owned(0).unwrap();
+            let mut expected = candles.clone();
+            expected.push_candle_unchecked(next, 20 * 60);
+            let series = indicator.compute_series(expected.view()).unwrap();
+            assert_eq!(indicator.update(next), series.last());
+        }
+    }
+
+    #[test]
+    fn test_warmup_not_enough_data() {
+        let candles = candles_from_closes(&CLOSES[..20]);
+        let config = Config::wilder(20, 100);
+        let mut rsi = RsiState::new(&config);
+
+        assert!(matches!(
+            rsi.compute_series(candles.view()),
+            Err(Error::NotEnoughData)
+        ));
+        assert!(matches!(
+            rsi.seed(candles.view()),
+            Err(Error::NotEnoughData)
+        ));
+        assert!(matches!(
+            rsi.latest(candles.view()),
+            Err(Error::NotEnoughData)
+        ));
+    }
+}
@@ -0,0 +1,509 @@
+use crate::{
+    core::{Aligned, Candle, CandleSeriesView, Column, Error, MaskedColumn, Numeric},
+    indicators::{Indicator, MacdConfig, MovingAverage, MovingAverageState, ensure_warmup},
+};
+
+/// MACD values for a single candle.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Macd<T> {
+    /// Difference between the fast and slow moving averages.
+    pub macd: T,
+    /// Moving average of the MACD line.
+    pub signal: T,
+    /// Difference between the MACD and signal lines.
+    pub histogram: T,
+}
+
+/// MACD lines over a candle series.
+///
+/// The MACD line becomes valid after [`MacdConfig::line_warmup`] candles, the
+/// signal line and histogram after [`MacdConfig::warmup`] candles.
+#[derive(Debug, Clone, Default)]
+pub struct MacdSeries<T> {
+    pub macd: MaskedColumn<T>,
+    pub signal: MaskedColumn<T>,
+    pub histogram: MaskedColumn<T>,
+}
+
+impl<T> MacdSeries<T> {
+    /// Returns the number of elements in each line.
+    pub fn len(&self) -> usize {
+        self.macd.len()
+    }
+
+    /// Returns true if the lines contain no elements.
+    pub fn is_empty(&self) -> bool {
+        self.macd.is_empty()
+    }
+}
+
+impl<T: Copy + PartialEq> PartialEq for MacdSeries<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.macd == other.macd && self.signal == other.signal && self.histogram == other.histogram
+    }
+}
+
+impl<T: Copy> MacdSeries<T> {
+    /// Gets the values at `index`, or None if out of bounds or any line is masked.
+    pub fn get(&self, index: usize) -> Option<Macd<T>> {
+        Some(Macd {
+            macd: self.macd.get(index)?,
+            signal: self.signal.get(index)?,
+            histogram: self.histogram.get(index)?,
+        })
+    }
+
+    /// Returns the last values, or None if empty or any line is masked.
+    pub fn last(&self) -> Option<Macd<T>> {
+        self.get(self.len().checked_sub(1)?)
+    }
+}
+
+/// Calculate Moving Average Convergence Divergence (MACD) for a candle series.
+///
+/// MACD follows trend changes through the gap between a fast and a slow moving
+/// average of the closes. The signal line smooths that gap and the histogram shows
+/// how far the MACD line has moved away from it.
+///
+/// # Algorithm
+///
+/// ```text
+/// MACD = SMOOTH(close, fast) - SMOOTH(close, slow)
+/// signal = SIGNAL_SMOOTH(MACD, signal)
+/// histogram = MACD - signal
+/// ```
+///
+/// `SMOOTH` and `SIGNAL_SMOOTH` are selected by `config.smoothing` and
+/// `config.signal_smoothing`. With EMA smoothing every average is seeded with the
+/// simple mean of its first inputs and the signal line starts from the first valid
+/// MACD value. TA-Lib instead seeds the fast EMA with the closes just before the
+/// slow EMA becomes valid, so its early values differ until the seeds decay.
+///
+/// # Arguments
+/// * `candles` - Series of OHLC candles
+/// * `config` - Configuration with periods and smoothing types
+///
+/// # Returns
+/// The MACD, signal and histogram lines for the last `max_history` candles, aligned
+/// with the candle each value belongs to, where values computed from fewer candles
+/// than their warmup are masked.
+///
+/// # Errors
+/// Returns `Error::InvalidConfig` if the configuration is invalid, or
+/// `Error::NotEnoughData` if insufficient candles for calculation.
+pub fn macd_series<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
+    config: &MacdConfig,
+) -> Result<Aligned<MacdSeries<T>>, Error> {
+    config.validate()?;
+    let candles = candles.into();
+    ensure_warmup(candles.len(), config.warmup())?;
+
+    let start = macd_window_start(candles.len(), config);
+    let closes = Column::from(candles.closes()[start..].to_vec());
+
+    let fast = closes.moving_average(MovingAverage::from_smoothing(config.smoothing, config.fast));
+    let slow = closes.moving_average(MovingAverage::from_smoothing(config.smoothing, config.slow));
+    let macd: MaskedColumn<T> = fast
+        .iter()
+        .zip(slow.iter())
+        .map(|(fast, slow)| Some(fast? - slow?))
+        .collect();
+
+    // The signal line only averages valid MACD values
+    let signal: MaskedColumn<T> = match macd.first_valid() {
+        Some(first) => {
+            let line = Column::from(macd.values()[first..].to_vec());
+            let average = MovingAverage::from_smoothing(config.signal_smoothing, config.signal);
+            std::iter::repeat_n(None, first)
+                .chain(line.moving_average(average).iter())
+                .collect()
+        }
+        None => std::iter::repeat_n(None, macd.len()).collect(),
+    };
+
+    let histogram = macd
+        .iter()
+        .zip(signal.iter())
+        .map(|(macd, signal)| Some(macd? - signal?))
+        .collect();
+
+    let series = MacdSeries {
+        macd,
+        signal,
+        histogram,
+    };
+    Ok(Aligned::new(series, start, candles.timestamps()[start]))
+}
+
+/// Calculate the latest MACD values for a candle series.
+/// This is more efficient than `macd_series` when only the most recent values are needed.
+///
+/// The values depend only on the last `max_history` candles, so they do not
+/// change with how much older history the series retains.
+///
+/// # Errors
+/// Returns `Error::InvalidConfig` if the configuration is invalid, or
+/// `Error::NotEnoughData` if insufficient candles for calculation.
+pub fn macd_latest<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
+    config: &MacdConfig,
+) -> Result<Macd<T>, Error> {
+    MacdState::from_candles(candles, config)?
+        .value()
+        .ok_or(Error::NotEnoughData)
+}
+
+/// Incremental MACD state that can be updated per candle in O(1) for exponential
+/// smoothers and O(period) at most for the others.
+///
+/// Values produced here are identical to [`macd_series`] as long as the series fits
+/// within `max_history` candles. When seeded from a longer series, the state starts
+/// from the same window as [`macd_latest`] and then keeps smoothing without
+/// discarding older bars.
+#[derive(Debug, Clone)]
+pub struct MacdState<T> {
+    config: MacdConfig,
+    fast: MovingAverageState<T>,
+    slow: MovingAverageState<T>,
+    signal: MovingAverageState<T>,
+    macd: Option<T>,
+    count: usize,
+}
+
+impl<T: Numeric> MacdState<T> {
+    /// Creates an empty MACD state.
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`MacdState::try_new`].
+    pub fn new(config: &MacdConfig) -> Self {
+        Self::try_new(config).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates an empty MACD state, validating the configuration.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid; see
+    /// [`MacdConfig::validate`].
+    pub fn try_new(config: &MacdConfig) -> Result<Self, Error> {
+        config.validate()?;
+        Ok(Self {
+            config: config.clone(),
+            fast: MovingAverageState::new(MovingAverage::from_smoothing(
+                config.smoothing,
+                config.fast,
+            )),
+            slow: MovingAverageState::new(MovingAverage::from_smoothing(
+                config.smoothing,
+                config.slow,
+            )),
+            signal: MovingAverageState::new(MovingAverage::from_smoothing(
+                config.signal_smoothing,
+                config.signal,
+            )),
+            macd: None,
+            count: 0,
+        })
+    }
+
+    /// Creates a MACD state seeded from the closes of a candle series.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid, or
+    /// `Error::NotEnoughData` if insufficient candles for calculation.
+    pub fn from_candles<'a>(
+        candles: impl Into<CandleSeriesView<'a, T>>,
+        config: &MacdConfig,
+    ) -> Result<Self, Error>
+    where
+        T: 'a,
+    {
+        let mut state = Self::try_new(config)?;
+        let candles = candles.into();
+        ensure_warmup(candles.len(), config.warmup())?;
+
+        let closes = candles.closes();
+        for &close in &closes[macd_window_start(closes.len(), config)..] {
+            state.update(close);
+        }
+
+        Ok(state)
+    }
+
+    /// Folds the close of a completed candle into the state and returns the new MACD
+    /// values, or None until the signal line is ready.
+    pub fn update(&mut self, close: T) -> Option<Macd<T>> {
+        let fast = self.fast.update(close);
+        let slow = self.slow.update(close);
+        self.count += 1;
+
+        self.macd = fast.zip(slow).map(|(fast, slow)| fast - slow);
+        if let Some(macd) = self.macd {
+            self.signal.update(macd);
+        }
+
+        self.value()
+    }
+
+    /// Returns the MACD values for an in-progress candle without modifying the state,
+    /// or None if the signal line would not be ready after it.
+    pub fn peek(&self, close: T) -> Option<Macd<T>> {
+        let macd = self.fast.peek(close)? - self.slow.peek(close)?;
+        Some(macd_from_lines(macd, self.signal.peek(macd)?))
+    }
+
+    /// Returns the latest MACD values, or None until the signal line is ready.
+    pub fn value(&self) -> Option<Macd<T>> {
+        Some(macd_from_lines(self.macd?, self.signal.value()?))
+    }
+
+    /// Returns the latest MACD line value, or None until the slow average is ready.
+    pub fn macd(&self) -> Option<T> {
+        self.macd
+    }
+
+    /// Returns true once the signal line is valid.
+    pub fn is_ready(&self) -> bool {
+        self.value().is_some()
+    }
+
+    /// Returns the number of candles processed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the configuration used by this state.
+    pub fn config(&self) -> &MacdConfig {
+        &self.config
+    }
+}
+
+impl<T: Numeric> Indicator<T, Candle<T>, Macd<T>> for MacdState<T> {
+    fn warmup(&self) -> usize {
+        self.config.warmup()
+    }
+
+    fn compute_series(
+        &self,
+        candles: CandleSeriesView<'_, T>,
+    ) -> Result<Aligned<MaskedColumn<Macd<T>>>, Error> {
+        let series = macd_series(candles, &self.config)?;
+        Ok(series.map(|series| (0..series.len()).map(|i| series.get(i)).collect()))
+    }
+
+    fn latest(&self, candles: CandleSeriesView<'_, T>) -> Result<Macd<T>, Error> {
+        macd_latest(candles, &self.config)
+    }
+
+    fn seed(&mut self, candles: CandleSeriesView<'_, T>) -> Result<(), Error> {
+        *self = Self::from_candles(candles, &self.config)?;
+        Ok(())
+    }
+
+    fn update(&mut self, input: Candle<T>) -> Option<Macd<T>> {
+        MacdState::update(self, input.close)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+}
+
+/// Returns the index of the first candle within the last `max_history` candles.
+fn macd_window_start(len: usize, config: &MacdConfig) -> usize {
+    len.saturating_sub(config.max_history)
+}
+
+/// Assembles the MACD values from the MACD and signal lines.
+fn macd_from_lines<T: Numeric>(macd: T, signal: T) -> Macd<T> {
+    Macd {
+        macd,
+        signal,
+        histogram: macd - signal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        indicators::Smoothing,
+        test_utils::{CLOSES, candles_from_closes},
+    };
+
+    #[test]
+    fn test_macd_series() {
+        let candles = candles_from_closes(&CLOSES);
+        let config = MacdConfig::default();
+
+        // (MACD, signal, histogram) from the first candle with a histogram, from
+        // `scripts/reference_values.py`, with every EMA seeded with a simple mean
+        let expected = [
+            (0.378964937363655, 0.47411065529275553, -0.09514571792910054),
+            (
+                0.29213934727363267,
+                0.43771639368893095,
+                -0.14557704641529828,
+            ),
+            (
+                0.21440261033957597,
+                0.39305363701905993,
+                -0.17865102667948396,
+            ),
+            (
+                0.14227949609963275,
+                0.3428988088351745,
+                -0.20061931273554173,
+            ),
+            (0.07059011034355223, 0.28843706913685, -0.2178469587932978),
+            (
+                -0.008717415366696457,
+                0.22900617223614073,
+                -0.23772358760283718,
+            ),
+        ];
+
+        let macd = macd_series(&candles, &config).unwrap();
+        assert_eq!(macd.start(), 0);
+        assert_eq!(macd.len(), candles.len());
+        assert_eq!(macd.macd.first_valid(), Some(25));
+        assert_eq!(macd.histogram.first_valid(), Some(33));
+
+        for (i, (line, signal, histogram)) in expected.iter().enumerate() {
+            let value = macd.get(33 + i).unwrap();
+            assert!((value.macd - line).abs() < 1e-12, "MACD at {i}");
+            assert!((value.signal - signal).abs() < 1e-12, "signal at {i}");
+            assert!(
+                (value.histogram - histogram).abs() < 1e-12,
+                "histogram at {i}"
+            );
+        }
+
+        let latest = macd_latest(&candles, &config).unwrap();
+        assert_eq!(Some(latest), macd.last());
+    }
+
+    #[test]
+    fn test_macd_series_longer_than_max_history() {
+        let candles = candles_from_closes(&CLOSES);
+        let config = MacdConfig::new(5, 10, 4, 20);
+
+        let macd = macd_series(&candles, &config).unwrap();
+        assert_eq!(macd.start(), candles.len() - 20);
+        assert_eq!(macd.start_timestamp(), (candles.len() as u64 - 20) * 60);
+        assert_eq!(macd.len(), 20);
+        assert_eq!(macd.histogram.first_valid(), Some(config.warmup() - 1));
+
+        let window = candles
+            .view()
+            .slice_index(candles.len() - 20..candles.len());
+        let expected = macd_series(window, &config).unwrap();
+        assert_eq!(macd.inner(), expected.inner());
+        assert_eq!(macd_latest(&candles, &config).ok(), macd.last());
+    }
+
+    #[test]
+    fn test_macd_state_matches_series() {
+        let candles = candles_from_closes(&CLOSES);
+
+        for config in [
+            MacdConfig::default(),
+            MacdConfig::new(5, 10, 4, 100)
+                .with_smoothing(Smoothing::Wma)
+                .with_signal_smoothing(Smoothing::Sma),
+        ] {
+            let series = macd_series(&candles, &config).unwrap();
+            let mut state = MacdState::new(&config);
+            for (i, close) in candles.closes().iter().enumerate() {
+                assert_eq!(state.peek(*close), series.get(i));
+                assert_eq!(state.update(*close), series.get(i));
+                assert_eq!(state.macd(), series.macd.get(i));
+            }
+            assert!(state.is_ready());
+            assert_eq!(state.count(), candles.len());
+        }
+    }
+
+    #[test]
+    fn test_macd_indicator() {
+        let candles = candles_from_closes(&CLOSES);
+        let config = MacdConfig::default();
+        let series = macd_series(&candles, &config).unwrap();
+
+        let mut indicator: Box<dyn Indicator<f64, Candle<f64>, Macd<f64>>> =
+            Box::new(MacdState::new(&config));
+        assert_eq!(indicator.warmup(), config.warmup());
+
+        let computed = indicator.compute_series(candles.view()).unwrap();
+        assert_eq!(computed.start(), series.start());
+        assert_eq!(computed.first_valid(), Some(config.warmup() - 1));
+        assert_eq!(indicator.latest(candles.view()).ok(), series.last());
+        for i in 0..candles.len() {
+            assert_eq!(computed.get(i), series.get(i));
+            let value = indicator.update(candles.get_owned(i).unwrap());
+            assert_eq!(value, series.get(i));
+        }
+
+        indicator.reset();
+        assert_eq!(indicator.update(candles.get_owned(0).unwrap()), None);
+
+        indicator.seed(candles.view()).unwrap();
+        let next = candles.get_owned(0).unwrap();
+        let mut expected = candles.clone();
+        expected.push_candle_unchecked(next, candles.len() as u64 * 60);
+        let series = indicator.compute_series(expected.view()).unwrap();
+        assert_eq!(indicator.update(next), series.last());
+    }
+
+    #[test]
+    fn test_macd_state_validation() {
+        let config = MacdConfig {
+            fast: 26,
+            ..MacdConfig::default()
+        };
+        assert!(matches!(
+            MacdState::<f64>::try_new(&config),
+            Err(Error::InvalidConfig(_))
+        ));
+        assert!(MacdState::<f64>::try_new(&MacdConfig::default()).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "fast")]
+    fn test_macd_state_fast_not_below_slow_panics() {
+        let config = MacdConfig {
+            slow: 12,
+            ..MacdConfig::default()
+        };
+        MacdState::<f64>::new(&config);
+    }
+
+    #[test]
+    fn test_macd_not_enough_data() {
+        let candles = candles_from_closes(&CLOSES);
+        let config = MacdConfig::new(12, 26, 20, 100);
+
+        assert!(matches!(
+            macd_series(&candles, &config),
+            Err(Error::NotEnoughData)
+        ));
+        assert!(matches!(
+            macd_latest(&candles, &config),
+            Err(Error::NotEnoughData)
+        ));
+
+        // A window too short for the signal line is rejected up front
+        let config = MacdConfig {
+            max_history: 30,
+            ..MacdConfig::default()
+        };
+        assert!(matches!(
+            macd_latest(&candles, &config),
+            Err(Error::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            macd_series(&candles, &config),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
@@ -1,9 +1,17 @@
-//! Technical analysis indicators (RSI, NATR, etc.).
+//! Technical analysis indicators (RSI, NATR, MACD, moving averages, etc.).
 
 mod config;
+mod indicator;
+mod macd;
+mod moving_average;
 mod natr;
 mod rsi;
+mod smoothing;
 
 pub use config::*;
+pub use indicator::*;
+pub use macd::*;
+pub use moving_average::*;
 pub use natr::*;
 pub use rsi::*;
+pub(crate) use smoothing::*;
@@ -0,0 +1,779 @@
+//! Moving averages over columns, with streaming counterparts.
+
+use std::collections::VecDeque;
+
+use crate::{
+    core::{
+        Aligned, Candle, CandleSeriesView, Column, Error, Ewm, EwmMode, MaskedColumn, Numeric,
+        RollingMean, RollingWma,
+    },
+    indicators::{Config, Indicator, Smoothing, ensure_warmup},
+};
+
+/// Moving average and its parameters.
+///
+/// Exponential averages, including the stages of DEMA, TEMA, ZLEMA and T3, follow
+/// TA-Lib: each one is seeded with the simple mean of its first `period` inputs.
+/// Values are masked until an average has seen [`MovingAverage::warmup`] inputs,
+/// which is one more than the TA-Lib lookback.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::{core::Column, indicators::MovingAverage};
+///
+/// let closes = Column::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+/// let wma = closes.moving_average(MovingAverage::Wma(3));
+///
+/// assert_eq!(wma.first_valid(), Some(2));
+/// assert_eq!(wma.last(), Some((4.0 + 10.0 + 18.0) / 6.0));
+/// assert_eq!(MovingAverage::<f64>::Dema(3).warmup(), 5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MovingAverage<T> {
+    /// Simple moving average.
+    Sma(usize),
+    /// Exponential moving average with `alpha = 2 / (period + 1)`.
+    Ema(usize),
+    /// Wilder's moving average, also known as RMA, with `alpha = 1 / period`.
+    Wilder(usize),
+    /// Linearly weighted moving average.
+    Wma(usize),
+    /// Double exponential moving average, `2 * EMA - EMA(EMA)`.
+    Dema(usize),
+    /// Triple exponential moving average, `3 * EMA - 3 * EMA(EMA) + EMA(EMA(EMA))`.
+    Tema(usize),
+    /// Hull moving average, `WMA(2 * WMA(n / 2) - WMA(n), floor(sqrt(n)))`.
+    Hma(usize),
+    /// Zero-lag exponential moving average, the EMA of `2 * x[t] - x[t - lag]`
+    /// with `lag = (period - 1) / 2`.
+    ///
+    /// The EMA is seeded with the mean of the first `period` de-lagged inputs.
+    /// pandas-ta's `zlma` instead averages the defined inputs among the first
+    /// `period`, so its values start `lag` inputs earlier and differ until the seed decays.
+    Zlema(usize),
+    /// Kaufman's adaptive moving average, whose smoothing constant moves between
+    /// the EMA constants of `fast` and `slow` with the efficiency ratio over `period`.
+    Kama {
+        period: usize,
+        fast: usize,
+        slow: usize,
+    },
+    /// Arnaud Legoux moving average: a Gaussian-weighted window whose peak sits at
+    /// `offset` (0 for the oldest input, 1 for the newest) with width `period / sigma`.
+    ///
+    /// pandas-ta's `alma` applies the weights from the newest input backwards, so its
+    /// offset of 0.85 favours older inputs, and it starts one input later.
+    Alma { period: usize, offset: T, sigma: T },
+    /// Tillson's T3, a weighted combination of six chained EMAs with volume factor `v_factor`.
+    T3 { period: usize, v_factor: T },
+}
+
+impl<T: Numeric> MovingAverage<T> {
+    /// KAMA with the conventional fast and slow periods of 2 and 30.
+    pub fn kama(period: usize) -> Self {
+        MovingAverage::Kama {
+            period,
+            fast: 2,
+            slow: 30,
+        }
+    }
+
+    /// ALMA with the conventional offset of 0.85 and sigma of 6.
+    pub fn alma(period: usize) -> Self {
+        MovingAverage::Alma {
+            period,
+            offset: T::from_f64(0.85),
+            sigma: T::from_f64(6.0),
+        }
+    }
+
+    /// T3 with the conventional volume factor of 0.7.
+    pub fn t3(period: usize) -> Self {
+        MovingAverage::T3 {
+            period,
+            v_factor: T::from_f64(0.7),
+        }
+    }
+
+    /// Returns the moving average selected by a configuration's smoothing and period.
+    ///
+    /// The average uses the conventional alpha and is always SMA-seeded, so it
+    /// reproduces the configuration's smoothing when `ewm_mode` is
+    /// `EwmMode::SmaSeeded(period)`, but not with the default recursive mode.
+    pub fn from_config(config: &Config<T>) -> Self {
+        Self::from_smoothing(config.smoothing, config.period)
+    }
+
+    /// Returns the moving average of `period` values for a smoothing type.
+    pub fn from_smoothing(smoothing: Smoothing, period: usize) -> Self {
+        match smoothing {
+            Smoothing::Sma => MovingAverage::Sma(period),
+            Smoothing::Ema => MovingAverage::Ema(period),
+            Smoothing::Wilder => MovingAverage::Wilder(period),
+            Smoothing::Wma => MovingAverage::Wma(period),
+            Smoothing::Dema => MovingAverage::Dema(period),
+            Smoothing::Tema => MovingAverage::Tema(period),
+        }
+    }
+
+    /// Checks that the average can be computed.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the period, or a KAMA fast or slow period,
+    /// is zero, or if an ALMA sigma is not positive.
+    pub fn validate(&self) -> Result<(), Error> {
+        let has_periods = match *self {
+            MovingAverage::Kama { period, fast, slow } => period > 0 && fast > 0 && slow > 0,
+            _ => self.period() > 0,
+        };
+        if !has_periods {
+            return Err(Error::InvalidConfig(
+                "period must be greater than zero".to_string(),
+            ));
+        }
+        if let MovingAverage::Alma { sigma, .. } = self
+            && !sigma.is_positive()
+        {
+            return Err(Error::InvalidConfig(format!(
+                "ALMA sigma must be positive, got {sigma:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the number of inputs averaged by the innermost window.
+    pub fn period(&self) -> usize {
+        match *self {
+            MovingAverage::Sma(period)
+            | MovingAverage::Ema(period)
+            | MovingAverage::Wilder(period)
+            | MovingAverage::Wma(period)
+            | MovingAverage::Dema(period)
+            | MovingAverage::Tema(period)
+            | MovingAverage::Hma(period)
+            | MovingAverage::Zlema(period)
+            | MovingAverage::Kama { period, .. }
+            | MovingAverage::Alma { period, .. }
+            | MovingAverage::T3 { period, .. } => period,
+        }
+    }
+
+    /// Returns the number of inputs required before the average is valid.
+    pub fn warmup(&self) -> usize {
+        let period = self.period();
+        match self {
+            MovingAverage::Sma(_)
+            | MovingAverage::Ema(_)
+            | MovingAverage::Wilder(_)
+            | MovingAverage::Wma(_)
+            | MovingAverage::Alma { .. } => period,
+            MovingAverage::Dema(_) => 2 * period - 1,
+            MovingAverage::Tema(_) => 3 * period - 2,
+            MovingAverage::Hma(_) => period + hull_sqrt_period(period) - 1,
+            MovingAverage::Zlema(_) => zlema_lag(period) + period,
+            MovingAverage::Kama { .. } => period + 1,
+            MovingAverage::T3 { .. } => 6 * period - 5,
+        }
+    }
+}
+
+impl<T: Numeric> Column<T> {
+    /// Calculates a moving average of the column.
+    ///
+    /// Values before the average has seen [`MovingAverage::warmup`] inputs are masked.
+    ///
+    /// # Panics
+    /// Panics if the average is invalid; see [`MovingAverage::validate`].
+    pub fn moving_average(&self, average: MovingAverage<T>) -> MaskedColumn<T> {
+        moving_average(self.as_slice(), average)
+    }
+
+    /// Calculates a moving average of the column, validating its parameters.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the average is invalid; see
+    /// [`MovingAverage::validate`].
+    pub fn try_moving_average(&self, average: MovingAverage<T>) -> Result<MaskedColumn<T>, Error> {
+        average.validate()?;
+        Ok(moving_average(self.as_slice(), average))
+    }
+}
+
+fn moving_average<T: Numeric>(values: &[T], average: MovingAverage<T>) -> MaskedColumn<T> {
+    let mut state = MovingAverageState::new(average);
+    values.iter().map(|value| state.update(*value)).collect()
+}
+
+/// Incremental moving average that can be updated per value in O(1), or O(period)
+/// for ALMA.
+///
+/// The state produces exactly the values of [`Column::moving_average`]. Driven as an
+/// [`Indicator`], it averages the closes of candles.
+#[derive(Debug, Clone)]
+pub struct MovingAverageState<T> {
+    average: MovingAverage<T>,
+    stages: Stages<T>,
+    count: usize,
+    value: Option<T>,
+}
+
+impl<T: Numeric> MovingAverageState<T> {
+    /// Creates an empty moving average.
+    ///
+    /// # Panics
+    /// Panics if the average is invalid; see [`MovingAverageState::try_new`].
+    pub fn new(average: MovingAverage<T>) -> Self {
+        Self::try_new(average).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates an empty moving average, validating its parameters.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the average is invalid; see
+    /// [`MovingAverage::validate`].
+    pub fn try_new(average: MovingAverage<T>) -> Result<Self, Error> {
+        average.validate()?;
+        Ok(Self {
+            average,
+            stages: Stages::new(average),
+            count: 0,
+            value: None,
+        })
+    }
+
+    /// Returns the moving average computed by this state.
+    pub fn average(&self) -> MovingAverage<T> {
+        self.average
+    }
+
+    /// Folds the next value into the average and returns the new average,
+    /// or None until the state is ready.
+    pub fn update(&mut self, value: T) -> Option<T> {
+        self.value = self.stages.update(value);
+        self.count += 1;
+        self.value
+    }
+
+    /// Returns the average after applying `value` without modifying the state,
+    /// or None if the state would not be ready after it.
+    pub fn peek(&self, value: T) -> Option<T> {
+        self.clone().update(value)
+    }
+
+    /// Returns the latest average, or None until the state is ready.
+    pub fn value(&self) -> Option<T> {
+        self.value
+    }
+
+    /// Returns true once the average is valid.
+    pub fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// Returns the number of values processed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl<T: Numeric> Indicator<T> for MovingAverageState<T> {
+    fn warmup(&self) -> usize {
+        self.average.warmup()
+    }
+
+    fn compute_series(
+        &self,
+        candles: CandleSeriesView<'_, T>,
+    ) -> Result<Aligned<MaskedColumn<T>>, Error> {
+        ensure_warmup(candles.len(), self.warmup())?;
+        let values = moving_average(candles.closes(), self.average);
+        Ok(Aligned::new(values, 0, candles.timestamps()[0]))
+    }
+
+    fn latest(&self, candles: CandleSeriesView<'_, T>) -> Result<T, Error> {
+        let mut state = self.clone();
+        Indicator::seed(&mut state, candles)?;
+        state.value.ok_or(Error::NotEnoughData)
+    }
+
+    fn seed(&mut self, candles: CandleSeriesView<'_, T>) -> Result<(), Error> {
+        ensure_warmup(candles.len(), self.warmup())?;
+        let mut state = Self::new(self.average);
+        for close in candles.closes() {
+            state.update(*close);
+        }
+        *self = state;
+        Ok(())
+    }
+
+    fn update(&mut self, input: Candle<T>) -> Option<T> {
+        MovingAverageState::update(self, input.close)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.average);
+    }
+}
+
+/// Running stages of each moving average.
+#[derive(Debug, Clone)]
+enum Stages<T> {
+    Sma(RollingMean<T>),
+    Ema(Ewm<T>),
+    Wma(RollingWma<T>),
+    Dema(EwmChain<T, 2>),
+    Tema(EwmChain<T, 3>),
+    Hma {
+        half: RollingWma<T>,
+        full: RollingWma<T>,
+        smooth: RollingWma<T>,
+    },
+    Zlema {
+        lag: usize,
+        window: VecDeque<T>,
+        ema: Ewm<T>,
+    },
+    Kama(Kama<T>),
+    Alma {
+        weights: Vec<T>,
+        window: VecDeque<T>,
+    },
+    T3 {
+        emas: EwmChain<T, 6>,
+        coefficients: [T; 4],
+    },
+}
+
+impl<T: Numeric> Stages<T> {
+    fn new(average: MovingAverage<T>) -> Self {
+        let period = average.period();
+        let ema = || ema(period);
+        match average {
+            MovingAverage::Sma(_) => Stages::Sma(RollingMean::new(period)),
+            MovingAverage::Ema(_) => Stages::Ema(ema()),
+            MovingAverage::Wilder(_) => Stages::Ema(Ewm::new(
+                T::ONE / T::from_usize(period),
+                EwmMode::SmaSeeded(period),
+                0,
+            )),
+            MovingAverage::Wma(_) => Stages::Wma(RollingWma::new(period)),
+            MovingAverage::Dema(_) => Stages::Dema(EwmChain::new(ema())),
+            MovingAverage::Tema(_) => Stages::Tema(EwmChain::new(ema())),
+            MovingAverage::Hma(_) => Stages::Hma {
+                half: RollingWma::new((period / 2).max(1)),
+                full: RollingWma::new(period),
+                smooth: RollingWma::new(hull_sqrt_period(period)),
+            },
+            MovingAverage::Zlema(_) => Stages::Zlema {
+                lag: zlema_lag(period),
+                window: VecDeque::with_capacity(zlema_lag(period) + 2),
+                ema: ema(),
+            },
+            MovingAverage::Kama { fast, slow, .. } => Stages::Kama(Kama::new(period, fast, slow)),
+            MovingAverage::Alma { offset, sigma, .. } => Stages::Alma {
+                weights: alma_weights(period, offset, sigma),
+                window: VecDeque::with_capacity(period + 1),
+            },
+            MovingAverage::T3 { v_factor, .. } => {
+                let a = v_factor;
+                let a2 = a * a;
+                let a3 = a2 * a;
+                let three = T::from_usize(3);
+                Stages::T3 {
+                    emas: EwmChain::new(ema()),
+                    coefficients: [
+                        T::ZERO - a3,
+                        three * a2 + three * a3,
+                        T::ZERO - T::from_usize(6) * a2 - three * a - three * a3,
+                        T::ONE + three * a + a3 + three * a2,
+                    ],
+                }
+            }
+        }
+    }
+
+    fn update(&mut self, value: T) -> Option<T> {
+        match self {
+            Stages::Sma(sma) => sma.push(value),
+            Stages::Ema(ema) => ema.push(value),
+            Stages::Wma(wma) => wma.push(value),
+            Stages::Dema(emas) => {
+                emas.push(value);
+                emas.values().map(dema)
+            }
+            Stages::Tema(emas) => {
+                emas.push(value);
+                emas.values().map(tema)
+            }
+            Stages::Hma { half, full, smooth } => {
+                // The half window is full whenever the full one is
+                let half = half.push(value);
+                let full = full.push(value)?;
+                smooth.push(T::two() * half? - full)
+            }
+            Stages::Zlema { lag, window, ema } => {
+                window.push_back(value);
+                if window.len() > *lag + 1 {
+                    window.pop_front();
+                }
+                if window.len() <= *lag {
+                    return None;
+                }
+                ema.push(T::two() * value - window[0])
+            }
+            Stages::Kama(kama) => kama.update(value),
+            Stages::Alma { weights, window } => {
+                window.push_back(value);
+                if window.len() > weights.len() {
+                    window.pop_front();
+                }
+                if window.len() < weights.len() {
+                    return None;
+                }
+                Some(
+                    weights
+                        .iter()
+                        .zip(window.iter())
+                        .map(|(w, v)| *w * *v)
+                        .sum(),
+                )
+            }
+            Stages::T3 { emas, coefficients } => {
+                emas.push(value);
+                let [_, _, e3, e4, e5, e6] = emas.values()?;
+                let [c1, c2, c3, c4] = *coefficients;
+                Some(c1 * e6 + c2 * e5 + c3 * e4 + c4 * e3)
+            }
+        }
+    }
+}
+
+/// Chain of exponential means, such as the stages of DEMA, TEMA and T3, where each
+/// stage averages the means of the stage before it once that stage is ready.
+#[derive(Debug, Clone)]
+pub(crate) struct EwmChain<T, const N: usize> {
+    stages: [Ewm<T>; N],
+}
+
+impl<T: Numeric, const N: usize> EwmChain<T, N> {
+    /// Creates a chain whose stages all start as copies of `ewm`.
+    pub(crate) fn new(ewm: Ewm<T>) -> Self {
+        Self {
+            stages: std::array::from_fn(|_| ewm.clone()),
+        }
+    }
+
+    /// Pushes a value into the first stage and passes it down the ready stages.
+    pub(crate) fn push(&mut self, value: T) {
+        let mut input = Some(value);
+        for stage in &mut self.stages {
+            match input {
+                Some(value) => input = stage.push(value),
+                None => return,
+            }
+        }
+    }
+
+    /// Returns the mean of every stage, or None until the last stage is ready.
+    pub(crate) fn values(&self) -> Option<[T; N]> {
+        self.stages.last()?.value()?;
+        Some(self.stages.each_ref().map(|stage| stage.mean()))
+    }
+
+    /// Returns the mean of every stage even while they are masked, where a stage
+    /// that has not been fed yet takes the mean of the stage before it, or None
+    /// before any input.
+    pub(crate) fn partial_values(&self) -> Option<[T; N]> {
+        let first = self.stages.first()?;
+        let mut before = (first.count() > 0).then(|| first.mean())?;
+        Some(self.stages.each_ref().map(|stage| {
+            if stage.count() > 0 {
+                before = stage.mean();
+            }
+            before
+        }))
+    }
+}
+
+/// Combines the stages of an EMA chain into a DEMA.
+pub(crate) fn dema<T: Numeric>([e1, e2]: [T; 2]) -> T {
+    T::two() * e1 - e2
+}
+
+/// Combines the stages of an EMA chain into a TEMA.
+pub(crate) fn tema<T: Numeric>([e1, e2, e3]: [T; 3]) -> T {
+    T::from_usize(3) * (e1 - e2) + e3
+}
+
+/// TA-Lib style exponential moving average seeded with a simple mean.
+fn ema<T: Numeric>(period: usize) -> Ewm<T> {
+    let alpha = T::two() / (T::from_usize(period) + T::ONE);
+    Ewm::new(alpha, EwmMode::SmaSeeded(period), 0)
+}
+
+/// Period of the final WMA of a Hull moving average.
+fn hull_sqrt_period(period: usize) -> usize {
+    period.isqrt().max(1)
+}
+
+/// Number of inputs a ZLEMA looks back to remove the lag of its EMA.
+fn zlema_lag(period: usize) -> usize {
+    period.saturating_sub(1) / 2
+}
+
+/// Normalized Gaussian weights of an ALMA window, from the oldest to the newest input.
+fn alma_weights<T: Numeric>(period: usize, offset: T, sigma: T) -> Vec<T> {
+    let m = offset * T::from_usize(period - 1);
+    let s = T::from_usize(period) / sigma;
+    let weights: Vec<T> = (0..period)
+        .map(|i| {
+            let d = T::from_usize(i) - m;
+            (T::ZERO - d * d / (T::two() * s * s)).exp()
+        })
+        .collect();
+    let total: T = weights.iter().copied().sum();
+    weights.into_iter().map(|w| w / total).collect()
+}
+
+/// Kaufman's adaptive moving average, following TA-Lib.
+#[derive(Debug, Clone)]
+struct Kama<T> {
+    period: usize,
+    fast: T,
+    slow: T,
+    // The last `period + 1` inputs and the sum of their absolute changes
+    window: VecDeque<T>,
+    noise: T,
+    value: Option<T>,
+}
+
+impl<T: Numeric> Kama<T> {
+    fn new(period: usize, fast: usize, slow: usize) -> Self {
+        Self {
+            period,
+            fast: T::two() / T::from_usize(fast + 1),
+            slow: T::two() / T::from_usize(slow + 1),
+            window: VecDeque::with_capacity(period + 2),
+            noise: T::ZERO,
+            value: None,
+        }
+    }
+
+    fn update(&mut self, value: T) -> Option<T> {
+        if let Some(&last) = self.window.back() {
+            self.noise += (value - last).abs();
+        }
+        self.window.push_back(value);
+        if self.window.len() > self.period + 1
+            && let Some(old) = self.window.pop_front()
+        {
+            self.noise -= (self.window[0] - old).abs();
+        }
+        if self.window.len() <= self.period {
+            return None;
+        }
+
+        let signal = (value - self.window[0]).abs();
+        let efficiency = if self.noise.is_positive() {
+            signal / self.noise
+        } else {
+            T::ONE
+        };
+        let constant = efficiency * (self.fast - self.slow) + self.slow;
+        let constant = constant * constant;
+
+        // The first value starts from the input before it
+        let prev = self.value.unwrap_or(self.window[self.window.len() - 2]);
+        let next = prev + constant * (value - prev);
+        self.value = Some(next);
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{CLOSES, candles_from_closes};
+
+    #[test]
+    fn test_moving_averages() {
+        // (average, first valid index, first three valid values, last value) for the
+        // averages that follow TA-Lib, or pandas-ta for HMA, as printed by
+        // `scripts/reference_values.py`. The script prints the library output next to
+        // its own when `talib` and `pandas_ta` are installed
+        let library_cases = [
+            (
+                MovingAverage::Sma(5),
+                4,
+                [48.577999999999996, 48.752, 48.844],
+                49.596000000000004,
+            ),
+            (
+                MovingAverage::Ema(5),
+                4,
+                [48.577999999999996, 48.72866666666666, 48.84244444444444],
+                49.61899973486487,
+            ),
+            (
+                MovingAverage::Wma(5),
+                4,
+                [48.656666666666666, 48.80733333333333, 48.913333333333334],
+                49.492000000000004,
+            ),
+            (
+                MovingAverage::Dema(5),
+                8,
+                [49.7178074074074, 50.03866721536351, 49.858086328303614],
+                49.27434771287823,
+            ),
+            (
+                MovingAverage::Tema(4),
+                9,
+                [50.10763123200002, 49.72395097344001, 49.54074438758399],
+                49.21455131004773,
+            ),
+            (
+                MovingAverage::Hma(9),
+                10,
+                [50.07277777777778, 49.97581481481482, 49.82496296296296],
+                49.204111111111104,
+            ),
+            (
+                MovingAverage::kama(10),
+                10,
+                [50.053062371579685, 50.006950596781756, 49.982988743002096],
+                49.903137541427505,
+            ),
+            (
+                MovingAverage::t3(4),
+                18,
+                [50.37701864371917, 50.65866557536421, 50.86765089246839],
+                49.54630571765145,
+            ),
+        ];
+
+        // Wilder's average has no public TA-Lib or pandas-ta function, and ZLEMA and
+        // ALMA deliberately differ from pandas-ta, so these only come from the script
+        let reference_cases = [
+            (
+                MovingAverage::Wilder(5),
+                4,
+                [48.577999999999996, 48.6684, 48.74872],
+                49.88537162336167,
+            ),
+            (
+                MovingAverage::Zlema(6),
+                7,
+                [49.193333333333335, 49.63809523809524, 50.010068027210885],
+                49.34161018106709,
+            ),
+            (
+                MovingAverage::alma(9),
+                8,
+                [49.30090565540416, 49.615294721115475, 49.747415116781404],
+                49.54417428987441,
+            ),
+        ];
+
+        let values = Column::from(CLOSES.to_vec());
+        for (average, first_valid, first_values, last) in
+            library_cases.into_iter().chain(reference_cases)
+        {
+            let result = values.moving_average(average);
+            assert_eq!(result.len(), values.len());
+            assert_eq!(result.first_valid(), Some(first_valid), "{average:?}");
+            assert_eq!(average.warmup(), first_valid + 1, "{average:?}");
+            for (i, expected) in first_values.iter().enumerate() {
+                let value = result.get(first_valid + i).unwrap();
+                assert!((value - expected).abs() < 1e-12, "{average:?} at {i}");
+            }
+            assert!((result.last().unwrap() - last).abs() < 1e-12, "{average:?}");
+        }
+    }
+
+    #[test]
+    fn test_state_matches_series() {
+        let values = Column::from(CLOSES.to_vec());
+        let candles = candles_from_closes(&CLOSES);
+
+        for average in [
+            MovingAverage::Sma(5),
+            MovingAverage::Ema(5),
+            MovingAverage::Wilder(5),
+            MovingAverage::Wma(5),
+            MovingAverage::Dema(5),
+            MovingAverage::Tema(4),
+            MovingAverage::Hma(4),
+            MovingAverage::Zlema(5),
+            MovingAverage::kama(6),
+            MovingAverage::alma(9),
+            MovingAverage::T3 {
+                period: 3,
+                v_factor: 0.5,
+            },
+        ] {
+            let expected = values.moving_average(average);
+            let mut state = MovingAverageState::new(average);
+            for (i, value) in values.iter().enumerate() {
+                assert_eq!(state.peek(*value), expected.get(i), "{average:?} at {i}");
+                assert_eq!(state.update(*value), expected.get(i), "{average:?} at {i}");
+                assert_eq!(state.value(), expected.get(i), "{average:?} at {i}");
+            }
+            assert_eq!(state.count(), values.len());
+
+            let series = state.compute_series(candles.view()).unwrap();
+            assert_eq!(series.inner(), &expected);
+            assert_eq!(state.latest(candles.view()).ok(), expected.last());
+
+            // Seeding from a prefix and updating with the rest continues the series
+            let split = average.warmup() + 2;
+            state.seed(candles.view().slice_index(0..split)).unwrap();
+            for (i, value) in values.iter().enumerate().skip(split) {
+                let value = MovingAverageState::update(&mut state, *value);
+                assert_eq!(value, expected.get(i), "{average:?} at {i}");
+            }
+
+            state.reset();
+            assert_eq!(state.value(), None);
+        }
+
+        let config = Config::<f64>::wilder(14, 100);
+        assert_eq!(
+            MovingAverage::from_config(&config),
+            MovingAverage::Wilder(14)
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        for average in [
+            MovingAverage::Sma(0),
+            MovingAverage::Dema(0),
+            MovingAverage::Kama {
+                period: 10,
+                fast: 0,
+                slow: 30,
+            },
+            MovingAverage::Alma {
+                period: 9,
+                offset: 0.85,
+                sigma: 0.0,
+            },
+        ] {
+            assert!(matches!(average.validate(), Err(Error::InvalidConfig(_))));
+            assert!(MovingAverageState::try_new(average).is_err());
+            assert!(
+                Column::from(CLOSES.to_vec())
+                    .try_moving_average(average)
+                    .is_err()
+            );
+        }
+        assert!(MovingAverage::<f64>::t3(5).validate().is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be greater than zero")]
+    fn test_zero_period_panics() {
+        let _ = MovingAverageState::<f64>::new(MovingAverage::Wilder(0));
+    }
+}
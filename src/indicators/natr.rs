@@ -1,6 +1,9 @@
 use crate::{
-    core::{CandleSeries, Column, Error, Numeric},
-    indicators::Config,
+    core::{Aligned, Candle, CandleSeriesView, Error, MaskedColumn, Numeric},
+    indicators::{
+        Config, Indicator, Smoother, align, ensure_warmup, seeds_with_first_candle,
+        skipped_changes, smooth, unconverged_len, window_start,
+    },
 };
 
 /// Calculate Normalized Average True Range (NATR) for a candle series.
@@ -16,72 +19,271 @@ use crate::{
 ///             |high[i] - close[i-1]|,
 ///             |low[i] - close[i-1]|)
 ///
-/// ATR = EMA(TR, period)
+/// ATR = SMOOTH(TR, period)
 /// NATR = (ATR / close) * 100
 /// ```
 ///
+/// `SMOOTH` is selected by `config.smoothing`; Wilder's smoothing gives the classic ATR.
+/// `config.ewm_mode` selects pandas-ta's adjusted or TA-Lib's SMA-seeded exponential
+/// smoothing. A candle that closes at zero has a NATR of zero, so numeric types
+/// whose division panics, such as `Fixed`, never divide by zero.
+///
 /// # Arguments
 /// * `candles` - Series of OHLC candles with high, low, close data
 /// * `config` - Configuration with period and smoothing parameters
 ///
 /// # Returns
-/// A column of NATR values expressed as percentages
+/// A masked column of NATR values expressed as percentages for the last `max_history`
+/// candles, aligned with the candle each value belongs to, where values computed
+/// from fewer true ranges than the smoothing warmup are masked
 ///
 /// # Errors
-/// Returns `Error::NotEnoughData` if insufficient candles for calculation.
-pub fn natr_series<T: Numeric>(
-    candles: &CandleSeries<T>,
+/// Returns `Error::InvalidConfig` if the configuration is invalid, or
+/// `Error::NotEnoughData` if insufficient candles for calculation.
+pub fn natr_series<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
     config: &Config<T>,
-) -> Result<Column<T>, Error> {
-    // We need at least `period + 1` candles to calculate RSI
-    // Because we lose the first candle when calculating true range
-    if candles.len() < config.period + 1 {
-        return Err(Error::NotEnoughData);
-    }
+) -> Result<Aligned<MaskedColumn<T>>, Error> {
+    config.validate()?;
+    let candles = candles.into();
+    ensure_warmup(candles.len(), natr_warmup(config))?;
 
     let tr = candles.true_range(Some(config.max_history));
-    let atr = tr.into_ewm_mean(config.alpha);
-    let closes = candles.closes();
+    let atr = smooth(tr, config, skipped_changes(candles.len(), config));
+    // The true range only covers the last `max_history` candles
+    let closes = &candles.closes()[window_start(candles.len(), config)..];
 
-    let hundred = T::hundred();
-    Ok(atr
+    let values = atr
         .iter()
         .zip(closes.iter())
-        .map(|(atr_value, close)| {
-            if atr_value.is_zero() {
-                T::ZERO
-            } else {
-                hundred * (*atr_value / *close)
-            }
-        })
-        .collect())
+        .map(|(atr_value, close)| natr_from_atr(*atr_value, *close))
+        .collect();
+
+    let values = MaskedColumn::with_warmup(values, unconverged_len(candles.len(), config));
+    Ok(align(&candles, values, config))
 }
 
 /// Calculate the latest NATR value for a candle series.
 /// This is more efficient than `natr_series` when only the most recent value is needed.
-pub fn natr_latest<T: Numeric>(candles: &CandleSeries<T>, config: &Config<T>) -> Result<T, Error> {
-    if candles.len() < config.period + 1 {
-        return Err(Error::NotEnoughData);
+///
+/// Once the series is longer than `max_history`, the value depends only on the last
+/// `max_history + 1` candles, so it does not change with how much older history the
+/// series retains.
+///
+/// # Errors
+/// Returns `Error::InvalidConfig` if the configuration is invalid, or
+/// `Error::NotEnoughData` if insufficient candles for calculation.
+pub fn natr_latest<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
+    config: &Config<T>,
+) -> Result<T, Error> {
+    NatrState::from_candles(candles, config)?
+        .value()
+        .ok_or(Error::NotEnoughData)
+}
+
+/// Incremental NATR state that can be updated per candle in O(1) for exponential
+/// smoothers and O(period) at most for the others.
+///
+/// The state keeps the previous close and the running ATR, applying the same
+/// recursion as [`natr_series`], so values produced here are identical to the
+/// series computation as long as the series fits within `max_history`. When seeded
+/// from a longer series, the state starts from the same window as [`natr_latest`]
+/// and then keeps smoothing without discarding older bars.
+#[derive(Debug, Clone)]
+pub struct NatrState<T> {
+    config: Config<T>,
+    prev_close: Option<T>,
+    atr: Smoother<T>,
+    last_close: T,
+    count: usize,
+    changes: usize,
+}
+
+impl<T: Numeric> NatrState<T> {
+    /// Creates an empty NATR state.
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`NatrState::try_new`].
+    pub fn new(config: &Config<T>) -> Self {
+        Self::try_new(config).unwrap_or_else(|err| panic!("{err}"))
     }
 
-    let tr = candles.true_range(Some(config.max_history));
-    let atr = tr.into_ewm_mean(config.alpha);
-    let closes = candles.closes();
+    /// Creates an empty NATR state, validating the configuration.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid; see
+    /// [`Config::validate`].
+    pub fn try_new(config: &Config<T>) -> Result<Self, Error> {
+        config.validate()?;
+        Ok(Self {
+            config: config.clone(),
+            prev_close: None,
+            atr: Smoother::new(config),
+            last_close: T::ZERO,
+            count: 0,
+            changes: 0,
+        })
+    }
+
+    /// Creates a NATR state seeded from a candle series.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid, or
+    /// `Error::NotEnoughData` if insufficient candles for calculation.
+    pub fn from_candles<'a>(
+        candles: impl Into<CandleSeriesView<'a, T>>,
+        config: &Config<T>,
+    ) -> Result<Self, Error>
+    where
+        T: 'a,
+    {
+        let mut state = Self::try_new(config)?;
+        let candles = candles.into();
+        ensure_warmup(candles.len(), natr_warmup(config))?;
 
-    let latest_atr = atr.last().unwrap();
-    let latest_close = closes.last().unwrap();
+        let len = candles.len();
+        let start = window_start(len, config);
+
+        // The first true range in a truncated window uses the close before it
+        if start > 0 {
+            state.prev_close = Some(candles.closes()[start - 1]);
+        }
+        for i in start..len {
+            state.update(&candles.get_owned(i).unwrap());
+        }
+
+        Ok(state)
+    }
+
+    /// Folds a completed candle into the state and returns the new NATR value,
+    /// or None until the state is ready.
+    pub fn update(&mut self, candle: &Candle<T>) -> Option<T> {
+        if self.prev_close.is_some() || seeds_with_first_candle(&self.config) {
+            self.atr.update(self.true_range(candle));
+        }
+        self.changes = self.next_changes();
+        self.prev_close = Some(candle.close);
+        self.last_close = candle.close;
+        self.count += 1;
+
+        self.value()
+    }
+
+    /// Returns the NATR value for an in-progress candle without modifying the state,
+    /// or None if the state would not be ready after it.
+    pub fn peek(&self, candle: &Candle<T>) -> Option<T> {
+        if self.next_changes() < self.warmup_changes() {
+            return None;
+        }
+        let atr = self.atr.peek(self.true_range(candle));
+        Some(natr_from_atr(atr, candle.close))
+    }
+
+    /// Returns the latest NATR value, or None until the state is ready.
+    pub fn value(&self) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        Some(natr_from_atr(self.atr.value()?, self.last_close))
+    }
+
+    /// Returns true once enough true ranges against a previous close have been
+    /// processed to fill the smoothing warmup.
+    pub fn is_ready(&self) -> bool {
+        self.count > 0 && self.changes >= self.warmup_changes()
+    }
+
+    /// Returns the number of candles processed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the configuration used by this state.
+    pub fn config(&self) -> &Config<T> {
+        &self.config
+    }
 
-    if latest_atr.is_zero() {
-        Ok(T::ZERO)
+    /// Returns the number of true ranges required before the state is ready.
+    fn warmup_changes(&self) -> usize {
+        self.config.warmup()
+    }
+
+    /// Returns the number of true ranges against a previous close after applying the next candle.
+    fn next_changes(&self) -> usize {
+        self.changes + usize::from(self.prev_close.is_some())
+    }
+
+    /// Calculates the true range of the given candle against the previous close.
+    fn true_range(&self, candle: &Candle<T>) -> T {
+        match self.prev_close {
+            Some(prev_close) => {
+                let hl = candle.high - candle.low;
+                let hc = (candle.high - prev_close).abs();
+                let lc = (candle.low - prev_close).abs();
+                hl.max(hc).max(lc)
+            }
+            None => candle.high - candle.low,
+        }
+    }
+}
+
+impl<T: Numeric> Indicator<T> for NatrState<T> {
+    fn warmup(&self) -> usize {
+        natr_warmup(&self.config)
+    }
+
+    fn compute_series(
+        &self,
+        candles: CandleSeriesView<'_, T>,
+    ) -> Result<Aligned<MaskedColumn<T>>, Error> {
+        natr_series(candles, &self.config)
+    }
+
+    fn latest(&self, candles: CandleSeriesView<'_, T>) -> Result<T, Error> {
+        natr_latest(candles, &self.config)
+    }
+
+    fn seed(&mut self, candles: CandleSeriesView<'_, T>) -> Result<(), Error> {
+        *self = Self::from_candles(candles, &self.config)?;
+        Ok(())
+    }
+
+    fn update(&mut self, input: Candle<T>) -> Option<T> {
+        NatrState::update(self, &input)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+}
+
+/// Number of candles required to calculate NATR.
+///
+/// We need one more candle than the smoothing warmup because the first candle
+/// has no previous close to measure the true range against.
+fn natr_warmup<T: Numeric>(config: &Config<T>) -> usize {
+    config.warmup() + 1
+}
+
+/// Converts an ATR value into a percentage of the closing price, or zero if
+/// either is zero.
+fn natr_from_atr<T: Numeric>(atr: T, close: T) -> T {
+    if atr.is_zero() || close.is_zero() {
+        T::ZERO
     } else {
-        Ok(T::hundred() * (*latest_atr / *latest_close))
+        T::hundred() * (atr / close)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{Candle, CandleSeries};
+    use crate::{
+        core::{Candle, CandleSeries, EwmMode},
+        indicators::Smoothing,
+        test_utils::CLOSES,
+    };
 
     fn get_test_data() -> CandleSeries<f64> {
         let highs = vec![
@@ -98,13 +300,6 @@ mod tests {
             49.55, 49.38, 49.10,
         ];
 
-        let closes = vec![
-            48.16, 48.61, 48.75, 48.63, 48.74, 49.03, 49.07, 49.32, 49.91, 50.13, 49.53, 49.50,
-            49.75, 50.03, 49.61, 49.80, 50.20, 50.73, 50.94, 51.08, 50.97, 50.55, 50.42, 50.14,
-            50.23, 50.41, 50.53, 50.60, 50.81, 50.95, 51.01, 51.07, 50.65, 50.16, 49.85, 49.77,
-            49.66, 49.49, 49.21,
-        ];
-
         let timestamps: Vec<i64> = (0..highs.len() as i64).map(|i| i * 60).collect();
         let mut candles = CandleSeries::new(60);
 
@@ -114,7 +309,7 @@ mod tests {
                     open: 0.0, // Open is not used in NATR calculation
                     high: highs[i],
                     low: lows[i],
-                    close: closes[i],
+                    close: CLOSES[i],
                     volume: 0.0, // Volume is not used in NATR calculation
                 },
                 timestamps[i] as u64,
@@ -127,10 +322,9 @@ mod tests {
     fn test_natr_series() {
         let candles = get_test_data();
 
-        // This is not exactly same as pandas-ta, because pandas-ta use pandas' `adjust=True` mode
-        // This was ran with `adjust=False` mode
-        // (https://pandas.pydata.org/docs/reference/api/pandas.DataFrame.ewm.html)
-        // (https://github.com/Data-Analisis/Technical-Analysis-Indicators---Pandas/blob/master/pandas_ta/overlap/rma.py#L16)
+        // The default recursive smoothing, i.e. pandas' `ewm(adjust=False)`
+        // (https://pandas.pydata.org/docs/reference/api/pandas.DataFrame.ewm.html).
+        // pandas-ta itself uses `adjust=True`, see `test_natr_ewm_modes`.
         let expected_natr = vec![
             1.1932, 1.1795, 1.1714, 1.1703, 1.1400, 1.0955, 1.0831, 1.0797, 1.0438, 1.1151, 1.1457,
             1.1232, 1.1028, 1.1910, 1.2825, 1.3450, 1.4020, 1.3933, 1.3839, 1.3747, 1.3917, 1.3976,
@@ -138,17 +332,261 @@ mod tests {
             1.5477, 1.5658, 1.5694, 1.5662, 1.5802,
         ];
 
-        let config = Config::new_f64_wilder(14, 100);
+        let config = Config::wilder(14, 100);
         let natr = natr_series(&candles, &config).unwrap();
 
         assert_eq!(natr.len(), expected_natr.len() + 1); // 1 initial NaN value
+        assert_eq!(natr.first_valid(), Some(14));
 
+        // Compare raw values, including the unconverged ones that are masked
         for (i, &expected) in expected_natr.iter().enumerate() {
-            let natr_value = natr[i + 1];
+            let natr_value = natr.values()[i + 1];
             assert!(
                 (natr_value - expected).abs() < 0.01,
                 "Mismatch at index {i}"
             );
         }
     }
+
+    #[test]
+    fn test_natr_ewm_modes() {
+        let candles = get_test_data();
+
+        // pandas-ta: rma is `ewm(alpha=1/14, min_periods=14)` with pandas' default
+        // `adjust=True`, over true ranges starting at the second candle
+        let pandas_ta = [
+            1.2031, 1.3402, 1.4284, 1.5045, 1.4844, 1.4643, 1.4443, 1.4570, 1.4578, 1.4790, 1.4727,
+            1.4725, 1.4587, 1.4392, 1.4207, 1.4276, 1.4326, 1.4401, 1.4886, 1.5461, 1.5814, 1.5981,
+            1.5993, 1.5933, 1.6057,
+        ];
+        // TA-Lib NATR: the ATR starts from the mean of the first 14 true ranges
+        let talib = [
+            1.1446, 1.2396, 1.3055, 1.3657, 1.3597, 1.3528, 1.3458, 1.3646, 1.3724, 1.3969, 1.3971,
+            1.4018, 1.3947, 1.3822, 1.3696, 1.3790, 1.3868, 1.3967, 1.4445, 1.5011, 1.5373, 1.5560,
+            1.5603, 1.5577, 1.5723,
+        ];
+
+        for (mode, expected) in [
+            (EwmMode::Adjusted, pandas_ta),
+            (EwmMode::SmaSeeded(14), talib),
+        ] {
+            let config = Config::wilder(14, 100)
+                .with_ewm_mode(mode)
+                .with_min_periods(14);
+            let natr = natr_series(&candles, &config).unwrap();
+            assert_eq!(natr.first_valid(), Some(14));
+            for (i, &expected) in expected.iter().enumerate() {
+                let natr_value = natr.get(i + 14).unwrap();
+                assert!((natr_value - expected).abs() < 1e-4, "{mode:?} at {i}");
+            }
+            assert_eq!(natr.last(), natr_latest(&candles, &config).ok());
+
+            let mut state = NatrState::new(&config);
+            for i in 0..candles.len() {
+                let candle = candles.get_owned(i).unwrap();
+                assert_eq!(state.peek(&candle), natr.get(i), "{mode:?} at {i}");
+                assert_eq!(state.update(&candle), natr.get(i), "{mode:?} at {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_natr_state_matches_series() {
+        let candles = get_test_data();
+        let config = Config::wilder(14, 100);
+        let natr = natr_series(&candles, &config).unwrap();
+
+        let mut state = NatrState::new(&config);
+        for i in 0..candles.len() {
+            let candle = candles.get_owned(i).unwrap();
+            assert_eq!(state.peek(&candle), natr.get(i));
+            assert_eq!(state.update(&candle), natr.get(i));
+        }
+        assert_eq!(state.value(), natr.last());
+    }
+
+    #[test]
+    fn test_natr_smoothing_variants() {
+        let candles = get_test_data();
+
+        for smoothing in [
+            Smoothing::Sma,
+            Smoothing::Ema,
+            Smoothing::Wilder,
+            Smoothing::Wma,
+            Smoothing::Dema,
+            Smoothing::Tema,
+        ] {
+            let config = Config::ema(10, 100).with_smoothing(smoothing);
+            let natr = natr_series(&candles, &config).unwrap();
+            assert_eq!(natr.first_valid(), Some(smoothing.warmup(10)));
+            assert_eq!(natr.last(), natr_latest(&candles, &config).ok());
+
+            let mut state = NatrState::new(&config);
+            for i in 0..candles.len() {
+                let candle = candles.get_owned(i).unwrap();
+                assert_eq!(state.peek(&candle), natr.get(i), "{smoothing:?} at {i}");
+                assert_eq!(state.update(&candle), natr.get(i), "{smoothing:?} at {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_natr_period_one() {
+        let candles = get_test_data();
+        let config = Config::wilder(1, 100);
+        let natr = natr_series(&candles, &config).unwrap();
+        let tr = candles.true_range(None);
+
+        assert_eq!(natr.first_valid(), Some(1));
+        for (bar, value) in natr.valid_bars() {
+            assert_eq!(value, 100.0 * (tr[bar] / candles.closes()[bar]));
+        }
+        assert_eq!(natr.last(), natr_latest(&candles, &config).ok());
+    }
+
+    #[test]
+    fn test_natr_state_validation() {
+        let mut config = Config::wilder(14, 100);
+        assert!(NatrState::try_new(&config).is_ok());
+
+        config.alpha = 2.0;
+        assert!(matches!(
+            NatrState::try_new(&config),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha must be within (0, 1]")]
+    fn test_natr_state_invalid_alpha_panics() {
+        let mut config = Config::wilder(14, 100);
+        config.alpha = 2.0;
+        let _ = NatrState::new(&config);
+    }
+
+    #[test]
+    fn test_natr_series_longer_than_max_history() {
+        let candles = get_test_data();
+        let config = Config::wilder(14, 20);
+        let natr = natr_series(&candles, &config).unwrap();
+
+        assert_eq!(natr.len(), 20);
+        assert_eq!(natr.start(), 19);
+        assert_eq!(natr.end(), candles.len());
+        assert_eq!(natr.start_timestamp(), 19 * 60);
+        assert_eq!(natr.last(), Some(natr_latest(&candles, &config).unwrap()));
+
+        // Every value is normalized by the close of the candle it belongs to
+        let atr = smooth(candles.true_range(Some(20)), &config, 0);
+        for (bar, value) in natr.valid_bars() {
+            let close = candles.closes()[bar];
+            assert_eq!(value, 100.0 * (atr[bar - 19] / close));
+        }
+        assert_eq!(natr.get_bar(18), None);
+        assert_eq!(natr.get_bar(candles.len() - 1), natr.last());
+    }
+
+    #[test]
+    fn test_natr_latest_independent_of_stored_history() {
+        let candles = get_test_data();
+        let config = Config::wilder(14, 20);
+
+        // Dropping candles before the window and its lookback candle changes nothing
+        for end in 1..=candles.len() {
+            let full = candles.view().slice_index(0..end);
+            let bounded = candles.view().slice_index(end.saturating_sub(21)..end);
+            assert_eq!(
+                natr_latest(full, &config).ok(),
+                natr_latest(bounded, &config).ok(),
+                "bar {end}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_natr_state_from_candles() {
+        let candles = get_test_data();
+        let config = Config::wilder(14, 20);
+        let state = NatrState::from_candles(&candles, &config).unwrap();
+
+        let expected = natr_latest(&candles, &config).unwrap();
+        assert_eq!(state.value(), Some(expected));
+    }
+
+    #[test]
+    fn test_natr_zero_close_fixed() {
+        use crate::core::Fixed;
+
+        let candles = get_test_data();
+        let mut fixed_candles = CandleSeries::new(60);
+        for i in 0..candles.len() {
+            let candle = candles.get_owned(i).unwrap();
+            let price = |value: f64| Fixed::<4>::from_f64(value);
+            let close = if i == 20 { 0.0 } else { candle.close };
+            fixed_candles.push_candle_unchecked(
+                Candle {
+                    open: price(candle.open),
+                    high: price(candle.high),
+                    low: price(close.min(candle.low)),
+                    close: price(close),
+                    volume: price(candle.volume),
+                },
+                candles.timestamps()[i],
+            );
+        }
+
+        let config = Config::<Fixed<4>>::wilder(14, 100);
+        let natr = natr_series(&fixed_candles, &config).unwrap();
+        assert_eq!(natr.get(20), Some(Fixed::ZERO));
+        assert!(natr.get(21).unwrap().is_positive());
+
+        let window = fixed_candles.view().slice_index(0..21);
+        assert_eq!(natr_latest(window, &config).unwrap(), Fixed::ZERO);
+
+        let mut state =
+            NatrState::from_candles(fixed_candles.view().slice_index(0..20), &config).unwrap();
+        let zero_close = fixed_candles.get_owned(20).unwrap();
+        assert_eq!(state.update(&zero_close), Some(Fixed::ZERO));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_natr_series_decimal() {
+        use rust_decimal::{Decimal, prelude::ToPrimitive};
+
+        let candles = get_test_data();
+        let mut decimal_candles = CandleSeries::new(60);
+        for i in 0..candles.len() {
+            let candle = candles.get_owned(i).unwrap();
+            decimal_candles.push_candle_unchecked(
+                Candle {
+                    open: Decimal::from_f64(candle.open),
+                    high: Decimal::from_f64(candle.high),
+                    low: Decimal::from_f64(candle.low),
+                    close: Decimal::from_f64(candle.close),
+                    volume: Decimal::from_f64(candle.volume),
+                },
+                candles.timestamps()[i],
+            );
+        }
+
+        let config = Config::wilder(14, 100);
+        let decimal_config = Config::<Decimal>::wilder(14, 100);
+        let expected = natr_series(&candles, &config).unwrap();
+        let natr = natr_series(&decimal_candles, &decimal_config).unwrap();
+
+        assert_eq!(natr.mask(), expected.mask());
+        for (i, (value, expected)) in natr
+            .values()
+            .iter()
+            .zip(expected.values().iter())
+            .enumerate()
+        {
+            assert!(
+                (value.to_f64().unwrap() - expected).abs() < 1e-9,
+                "Mismatch at index {i}"
+            );
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
-    core::{CandleSeries, Column, Error, Numeric},
-    indicators::Config,
+    core::{Aligned, Candle, CandleSeriesView, Error, MaskedColumn, Numeric},
+    indicators::{
+        Config, Indicator, Smoother, align, ensure_warmup, seeds_with_first_candle,
+        skipped_changes, smooth, unconverged_len, window_start,
+    },
 };
 
 /// Calculate Relative Strength Index (RSI) for a candle series.
@@ -16,90 +19,291 @@ use crate::{
 /// positive = close[i] - close[i-1] if positive, else 0
 /// negative = |close[i] - close[i-1]| if negative, else 0
 ///
-/// avg_gain = EMA(positive, alpha)
-/// avg_loss = EMA(negative, alpha)
+/// avg_gain = SMOOTH(positive, period)
+/// avg_loss = SMOOTH(negative, period)
 ///
 /// RSI = 100 * avg_gain / (avg_gain + avg_loss)
 /// ```
 ///
+/// `SMOOTH` is selected by `config.smoothing`: Wilder's smoothing gives the
+/// classic RSI and a simple moving average gives Cutler's RSI. `config.ewm_mode`
+/// selects pandas-ta's adjusted or TA-Lib's SMA-seeded exponential smoothing.
+///
 /// # Arguments
 /// * `candles` - Series of OHLC candles
 /// * `config` - Configuration with period and smoothing parameters
 ///
 /// # Returns
-/// A column of RSI values (0-100 range)
+/// A masked column of RSI values (0-100 range) for the last `max_history` candles,
+/// aligned with the candle each value belongs to, where values computed from fewer
+/// price changes than the smoothing warmup are masked
 ///
 /// # Errors
-/// Returns `Error::NotEnoughData` if insufficient candles for calculation.
-pub fn rsi_series<T: Numeric>(
-    candles: &CandleSeries<T>,
+/// Returns `Error::InvalidConfig` if the configuration is invalid, or
+/// `Error::NotEnoughData` if insufficient candles for calculation.
+pub fn rsi_series<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
     config: &Config<T>,
-) -> Result<Column<T>, Error> {
-    // We need at least `period + 1` candles to calculate RSI
-    // Because we lose the first candle when calculating gains and losses
-    if candles.len() < config.period + 1 {
-        return Err(Error::NotEnoughData);
-    }
-
-    let closes = candles.closes();
-    let (gains, losses) = closes.gains_losses(Some(config.max_history));
+) -> Result<Aligned<MaskedColumn<T>>, Error> {
+    config.validate()?;
+    let candles = candles.into();
+    ensure_warmup(candles.len(), rsi_warmup(config))?;
 
-    let ema_gains = gains.into_ewm_mean(config.alpha);
-    let ema_losses = losses.into_ewm_mean(config.alpha);
+    let (gains, losses) = candles.gains_losses(Some(config.max_history));
 
-    let fifty = T::fifty();
-    let hundred = T::hundred();
+    let skip = skipped_changes(candles.len(), config);
+    let avg_gains = smooth(gains, config, skip);
+    let avg_losses = smooth(losses, config, skip);
 
-    Ok(ema_gains
+    let values = avg_gains
         .iter()
-        .zip(ema_losses.iter())
-        .map(|(gain, loss)| {
-            if gain.is_zero() && loss.is_zero() {
-                fifty
-            } else {
-                hundred * (*gain / (*gain + *loss))
-            }
-        })
-        .collect())
+        .zip(avg_losses.iter())
+        .map(|(gain, loss)| rsi_from_averages(*gain, *loss))
+        .collect();
+
+    let values = MaskedColumn::with_warmup(values, unconverged_len(candles.len(), config));
+    Ok(align(&candles, values, config))
 }
 
 /// Calculate the latest RSI value for a candle series.
 /// This is more efficient than `rsi_series` when only the most recent value is needed.
-pub fn rsi_latest<T: Numeric>(candles: &CandleSeries<T>, config: &Config<T>) -> Result<T, Error> {
-    if candles.len() < config.period + 1 {
-        return Err(Error::NotEnoughData);
+///
+/// Once the series is longer than `max_history`, the value depends only on the last
+/// `max_history + 1` candles, so it does not change with how much older history the
+/// series retains.
+///
+/// # Errors
+/// Returns `Error::InvalidConfig` if the configuration is invalid, or
+/// `Error::NotEnoughData` if insufficient candles for calculation.
+pub fn rsi_latest<'a, T: Numeric + 'a>(
+    candles: impl Into<CandleSeriesView<'a, T>>,
+    config: &Config<T>,
+) -> Result<T, Error> {
+    RsiState::from_candles(candles, config)?
+        .value()
+        .ok_or(Error::NotEnoughData)
+}
+
+/// Incremental RSI state that can be updated per candle in O(1) for exponential
+/// smoothers and O(period) at most for the others.
+///
+/// The state folds each close into smoothed averages of gains and losses using the
+/// same recursion as [`rsi_series`], so values produced here are identical to the
+/// series computation as long as the series fits within `max_history`. When seeded
+/// from a longer series, the state starts from the same window as [`rsi_latest`]
+/// and then keeps smoothing without discarding older bars.
+#[derive(Debug, Clone)]
+pub struct RsiState<T> {
+    config: Config<T>,
+    prev_close: Option<T>,
+    avg_gain: Smoother<T>,
+    avg_loss: Smoother<T>,
+    count: usize,
+    changes: usize,
+}
+
+impl<T: Numeric> RsiState<T> {
+    /// Creates an empty RSI state.
+    ///
+    /// # Panics
+    /// Panics if the configuration is invalid; see [`RsiState::try_new`].
+    pub fn new(config: &Config<T>) -> Self {
+        Self::try_new(config).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates an empty RSI state, validating the configuration.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid; see
+    /// [`Config::validate`].
+    pub fn try_new(config: &Config<T>) -> Result<Self, Error> {
+        config.validate()?;
+        Ok(Self {
+            config: config.clone(),
+            prev_close: None,
+            avg_gain: Smoother::new(config),
+            avg_loss: Smoother::new(config),
+            count: 0,
+            changes: 0,
+        })
+    }
+
+    /// Creates an RSI state seeded from the closes of a candle series.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the configuration is invalid, or
+    /// `Error::NotEnoughData` if insufficient candles for calculation.
+    pub fn from_candles<'a>(
+        candles: impl Into<CandleSeriesView<'a, T>>,
+        config: &Config<T>,
+    ) -> Result<Self, Error>
+    where
+        T: 'a,
+    {
+        let mut state = Self::try_new(config)?;
+        let candles = candles.into();
+        ensure_warmup(candles.len(), rsi_warmup(config))?;
+
+        let closes = candles.closes();
+        let len = closes.len();
+        let start = window_start(len, config);
+
+        // The first change in a truncated window is measured against the bar before it
+        if start > 0 {
+            state.prev_close = Some(closes[start - 1]);
+        }
+        for &close in &closes[start..] {
+            state.update(close);
+        }
+
+        Ok(state)
+    }
+
+    /// Folds the close of a completed candle into the state and returns the new RSI value,
+    /// or None until the state is ready.
+    pub fn update(&mut self, close: T) -> Option<T> {
+        if self.prev_close.is_some() || seeds_with_first_candle(&self.config) {
+            let (gain, loss) = self.gain_loss(close);
+            self.avg_gain.update(gain);
+            self.avg_loss.update(loss);
+        }
+        self.changes = self.next_changes();
+        self.prev_close = Some(close);
+        self.count += 1;
+
+        self.value()
+    }
+
+    /// Returns the RSI value for an in-progress candle without modifying the state,
+    /// or None if the state would not be ready after it.
+    pub fn peek(&self, close: T) -> Option<T> {
+        if self.next_changes() < self.warmup_changes() {
+            return None;
+        }
+        let (gain, loss) = self.gain_loss(close);
+        Some(rsi_from_averages(
+            self.avg_gain.peek(gain),
+            self.avg_loss.peek(loss),
+        ))
     }
 
-    let closes = candles.closes();
-    let (gains, losses) = closes.gains_losses(Some(config.max_history));
+    /// Returns the latest RSI value, or None until the state is ready.
+    pub fn value(&self) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        Some(rsi_from_averages(
+            self.avg_gain.value()?,
+            self.avg_loss.value()?,
+        ))
+    }
+
+    /// Returns true once enough price changes have been processed to fill the smoothing warmup.
+    pub fn is_ready(&self) -> bool {
+        self.count > 0 && self.changes >= self.warmup_changes()
+    }
 
-    let ema_gains = gains.into_ewm_mean(config.alpha);
-    let ema_losses = losses.into_ewm_mean(config.alpha);
+    /// Returns the number of candles processed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
 
-    let latest_gain = ema_gains.last().unwrap();
-    let latest_loss = ema_losses.last().unwrap();
+    /// Returns the configuration used by this state.
+    pub fn config(&self) -> &Config<T> {
+        &self.config
+    }
 
-    let rsi = if latest_gain.is_zero() && latest_loss.is_zero() {
+    /// Returns the number of price changes required before the state is ready.
+    fn warmup_changes(&self) -> usize {
+        self.config.warmup()
+    }
+
+    /// Returns the number of price changes observed after applying the next close.
+    fn next_changes(&self) -> usize {
+        self.changes + usize::from(self.prev_close.is_some())
+    }
+
+    /// Splits the change from the previous close into a gain and a loss.
+    fn gain_loss(&self, close: T) -> (T, T) {
+        let change = match self.prev_close {
+            Some(prev) => close - prev,
+            None => T::ZERO,
+        };
+        if change.is_positive() {
+            (change, T::ZERO)
+        } else {
+            (T::ZERO, change.abs())
+        }
+    }
+}
+
+impl<T: Numeric> Indicator<T> for RsiState<T> {
+    fn warmup(&self) -> usize {
+        rsi_warmup(&self.config)
+    }
+
+    fn compute_series(
+        &self,
+        candles: CandleSeriesView<'_, T>,
+    ) -> Result<Aligned<MaskedColumn<T>>, Error> {
+        rsi_series(candles, &self.config)
+    }
+
+    fn latest(&self, candles: CandleSeriesView<'_, T>) -> Result<T, Error> {
+        rsi_latest(candles, &self.config)
+    }
+
+    fn seed(&mut self, candles: CandleSeriesView<'_, T>) -> Result<(), Error> {
+        *self = Self::from_candles(candles, &self.config)?;
+        Ok(())
+    }
+
+    fn update(&mut self, input: Candle<T>) -> Option<T> {
+        RsiState::update(self, input.close)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(&self.config);
+    }
+}
+
+/// Number of candles required to calculate RSI.
+///
+/// We need one more candle than the smoothing warmup because we lose the first
+/// candle when calculating gains and losses.
+fn rsi_warmup<T: Numeric>(config: &Config<T>) -> usize {
+    config.warmup() + 1
+}
+
+/// Converts smoothed gain and loss into an RSI value.
+fn rsi_from_averages<T: Numeric>(gain: T, loss: T) -> T {
+    if gain.is_zero() && loss.is_zero() {
         T::fifty()
     } else {
-        T::hundred() * (*latest_gain / (*latest_gain + *latest_loss))
-    };
-
-    Ok(rsi)
+        T::hundred() * (gain / (gain + loss))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::CandleSeries;
+    use crate::{
+        core::{CandleSeries, Column, EwmMode},
+        indicators::{MovingAverage, Smoothing},
+        test_utils::{candles_from_closes, candles_from_closes_at},
+    };
 
-    #[test]
-    fn test_rsi_series() {
-        let prices = vec![
+    fn get_test_prices() -> Vec<f64> {
+        vec![
             44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03,
             45.61, 46.28, 46.28, 46.00, 46.03, 46.41, 46.22, 45.64, 46.21, 46.25, 45.71, 46.45,
             45.78, 45.35, 44.03, 44.18, 44.22, 44.57, 43.42, 42.66, 43.13,
-        ];
+        ]
+    }
+
+    #[test]
+    fn test_rsi_series() {
+        let prices = get_test_prices();
 
         // Ran from pandas-ta, the initial 14 values are NaN
         let expected_rsi = vec![
@@ -107,22 +311,307 @@ mod tests {
             36.42, 38.17, 38.66, 42.89, 34.47, 30.25, 35.51,
         ];
 
-        let mut candles = CandleSeries::new(60);
+        let candles = candles_from_closes(&prices);
+
+        let config = Config::wilder(14, 100);
+
+        let rsi_values = rsi_series(&candles, &config).unwrap();
+
+        assert_eq!(rsi_values.len(), expected_rsi.len() + 14);
+        assert_eq!(rsi_values.first_valid(), Some(14));
+
+        for (i, &expected) in expected_rsi.iter().enumerate() {
+            let rsi_value = rsi_values.get(i + 14).unwrap();
+            assert!(
+                (rsi_value - expected).abs() < 0.01,
+                "RSI value mismatch at index {i}"
+            );
+        }
+    }
 
+    #[test]
+    fn test_rsi_state_matches_series() {
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
+
+        let config = Config::wilder(14, 100);
+        let rsi_values = rsi_series(&candles, &config).unwrap();
+
+        let mut state = RsiState::new(&config);
         for (i, &price) in prices.iter().enumerate() {
+            assert_eq!(state.peek(price), rsi_values.get(i));
+            assert_eq!(state.update(price), rsi_values.get(i));
+        }
+        assert!(state.is_ready());
+    }
+
+    #[test]
+    fn test_rsi_state_from_candles() {
+        let prices = get_test_prices();
+        let mut candles = candles_from_closes(&prices[..20]);
+
+        // Seeding from a truncated window matches the windowed latest value
+        let config = Config::wilder(14, 16);
+        let state = RsiState::from_candles(&candles, &config).unwrap();
+        assert_eq!(state.value(), Some(rsi_latest(&candles, &config).unwrap()));
+
+        // Without truncation, updates continue to match the series exactly
+        let config = Config::wilder(14, 100);
+        let mut state = RsiState::from_candles(&candles, &config).unwrap();
+        for (i, &price) in prices.iter().enumerate().skip(20) {
             candles.push(price, 0.0, (i as u64) * 60).unwrap();
+            assert_eq!(state.update(price), rsi_latest(&candles, &config).ok());
         }
+    }
 
-        let config = Config::new_f64_wilder(14, 100);
+    #[test]
+    fn test_rsi_series_longer_than_max_history() {
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
 
+        let config = Config::wilder(14, 16);
         let rsi_values = rsi_series(&candles, &config).unwrap();
+        let start = prices.len() - 16;
+        assert_eq!(rsi_values.start(), start);
+        assert_eq!(rsi_values.start_timestamp(), (start as u64) * 60);
+        assert_eq!(rsi_values.end(), prices.len());
 
-        assert_eq!(rsi_values.len(), expected_rsi.len() + 14);
+        assert_eq!(rsi_values.last(), rsi_latest(&candles, &config).ok());
 
-        for (i, &expected) in expected_rsi.iter().enumerate() {
-            let rsi_value = rsi_values[i + 14];
+        // Dropping bars before the window and its lookback candle changes nothing
+        let view = candles.view().slice_index(start - 1..prices.len());
+        let from_view = rsi_series(view, &config).unwrap();
+        assert_eq!(from_view.start(), 1);
+        assert_eq!(from_view.inner(), rsi_values.inner());
+        for (bar, value) in rsi_values.valid_bars() {
+            assert_eq!(from_view.get_bar(bar + 1 - start), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_rsi_latest_independent_of_stored_history() {
+        let prices = get_test_prices();
+        let config = Config::wilder(14, 16);
+
+        // A series that stores only max_history + 1 candles gives the same values
+        let full = candles_from_closes(&prices);
+        let mut bounded = CandleSeries::with_max_len(60, 17);
+        for (i, &price) in prices.iter().enumerate() {
+            bounded.push(price, 0.0, (i as u64) * 60).unwrap();
+            assert_eq!(
+                rsi_latest(full.view().slice_index(0..i + 1), &config).ok(),
+                rsi_latest(&bounded, &config).ok(),
+                "bar {i}"
+            );
+        }
+        assert!(rsi_latest(&bounded, &config).is_ok());
+
+        // Any amount of history before the window and its lookback candle changes nothing
+        for start in 0..prices.len() - 17 {
+            let view = full.view().slice_index(start..prices.len());
+            assert_eq!(
+                rsi_latest(view, &config).ok(),
+                rsi_latest(&full, &config).ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cutler_rsi() {
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
+
+        let config = Config::ema(14, 100).with_smoothing(Smoothing::Sma);
+        let rsi_values = rsi_series(&candles, &config).unwrap();
+        assert_eq!(rsi_values.first_valid(), Some(14));
+
+        // Cutler's RSI uses plain sums of gains and losses over the last 14 changes
+        for i in 14..prices.len() {
+            let (mut gain, mut loss) = (0.0, 0.0);
+            for j in i - 13..=i {
+                let change = prices[j] - prices[j - 1];
+                if change > 0.0 {
+                    gain += change;
+                } else {
+                    loss -= change;
+                }
+            }
+            let expected = 100.0 * gain / (gain + loss);
+            assert!((rsi_values.get(i).unwrap() - expected).abs() < 1e-9);
+        }
+        assert_eq!(rsi_values.last(), rsi_latest(&candles, &config).ok());
+
+        let mut state = RsiState::new(&config);
+        for (i, &price) in prices.iter().enumerate() {
+            assert_eq!(state.update(price), rsi_values.get(i));
+        }
+    }
+
+    #[test]
+    fn test_wilder_rsi() {
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
+
+        let config = Config::wilder(14, 100).with_ewm_mode(EwmMode::SmaSeeded(14));
+        let rsi_values = rsi_series(&candles, &config).unwrap();
+        assert_eq!(rsi_values.first_valid(), Some(14));
+
+        // Wilder's RSI starts from the mean gain and loss of the first 14 changes,
+        // then adds each change with a weight of 1/14
+        let (mut gain, mut loss) = (0.0, 0.0);
+        for i in 1..prices.len() {
+            let change = prices[i] - prices[i - 1];
+            let (g, l) = (change.max(0.0), (-change).max(0.0));
+            if i <= 14 {
+                gain += g / 14.0;
+                loss += l / 14.0;
+            } else {
+                gain = (gain * 13.0 + g) / 14.0;
+                loss = (loss * 13.0 + l) / 14.0;
+            }
+            if i >= 14 {
+                let expected = 100.0 * gain / (gain + loss);
+                assert!((rsi_values.get(i).unwrap() - expected).abs() < 1e-9);
+            }
+        }
+
+        // Smoothing the gains with Wilder's moving average gives the same averages
+        let (gains, _) = candles.view().gains_losses(None);
+        let tail = Column::from(gains.as_slice()[1..].to_vec());
+        let average = tail.moving_average(MovingAverage::from_config(&config));
+        assert!((average.last().unwrap() - gain).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rsi_ewm_modes() {
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
+
+        // pandas-ta: rma is `ewm(alpha=1/14, min_periods=14)` with pandas' default
+        // `adjust=True`. The seed cancels out of RSI, so this matches `test_rsi_series`.
+        let pandas_ta = [
+            71.8024, 65.1866, 65.5528, 69.8756, 65.4528, 54.1793, 61.2441, 61.6905, 52.8419,
+            61.0804, 52.1903, 47.4199, 36.4162, 38.1720, 38.6584, 42.8922, 34.4730, 30.2475,
+            35.5119,
+        ];
+        // TA-Lib RSI: the averages start from the mean of the first 14 changes
+        let talib = [
+            70.4641, 66.2496, 66.4809, 69.3469, 66.2947, 57.9150, 62.8807, 63.2088, 56.0116,
+            62.3399, 54.6710, 50.3868, 40.0194, 41.4926, 41.9024, 45.4995, 37.3228, 33.0905,
+            37.7888,
+        ];
+
+        for (mode, expected) in [
+            (EwmMode::Adjusted, pandas_ta),
+            (EwmMode::SmaSeeded(14), talib),
+        ] {
+            let config = Config::wilder(14, 100)
+                .with_ewm_mode(mode)
+                .with_min_periods(14);
+            let rsi_values = rsi_series(&candles, &config).unwrap();
+            assert_eq!(rsi_values.first_valid(), Some(14));
+            for (i, &expected) in expected.iter().enumerate() {
+                let rsi_value = rsi_values.get(i + 14).unwrap();
+                assert!((rsi_value - expected).abs() < 1e-4, "{mode:?} at {i}");
+            }
+            assert_eq!(rsi_values.last(), rsi_latest(&candles, &config).ok());
+
+            let mut state = RsiState::new(&config);
+            for (i, &price) in prices.iter().enumerate() {
+                assert_eq!(state.peek(price), rsi_values.get(i), "{mode:?} at {i}");
+                assert_eq!(state.update(price), rsi_values.get(i), "{mode:?} at {i}");
+            }
+
+            // Truncated windows start from the change before them in every mode
+            let mut truncated = config;
+            truncated.max_history = 20;
+            let state = RsiState::from_candles(&candles, &truncated).unwrap();
+            assert_eq!(state.value(), rsi_latest(&candles, &truncated).ok());
+        }
+    }
+
+    #[test]
+    fn test_rsi_period_one() {
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
+
+        // With alpha = 1 each value only reflects the latest change
+        let config = Config::wilder(1, 100);
+        let rsi_values = rsi_series(&candles, &config).unwrap();
+        assert_eq!(rsi_values.first_valid(), Some(1));
+        for i in 1..prices.len() {
+            let expected = match prices[i].partial_cmp(&prices[i - 1]).unwrap() {
+                std::cmp::Ordering::Greater => 100.0,
+                std::cmp::Ordering::Less => 0.0,
+                std::cmp::Ordering::Equal => 50.0,
+            };
+            assert_eq!(rsi_values.get(i), Some(expected), "bar {i}");
+        }
+
+        let mut state = RsiState::new(&config);
+        for (i, &price) in prices.iter().enumerate() {
+            assert_eq!(state.update(price), rsi_values.get(i));
+        }
+    }
+
+    #[test]
+    fn test_rsi_state_validation() {
+        let mut config = Config::wilder(14, 100);
+        assert!(RsiState::try_new(&config).is_ok());
+
+        config.alpha = 2.0;
+        assert!(matches!(
+            RsiState::try_new(&config),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha must be within (0, 1]")]
+    fn test_rsi_state_invalid_alpha_panics() {
+        let mut config = Config::wilder(14, 100);
+        config.alpha = 2.0;
+        let _ = RsiState::new(&config);
+    }
+
+    #[test]
+    fn test_rsi_series_on_view() {
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
+        let tail = candles_from_closes_at(&prices[10..], 10);
+
+        let config = Config::wilder(14, 100);
+        let view = candles.slice(600..u64::MAX);
+        assert_eq!(
+            rsi_series(view, &config).unwrap(),
+            rsi_series(&tail, &config).unwrap()
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_rsi_series_decimal() {
+        use rust_decimal::{Decimal, prelude::ToPrimitive};
+
+        let prices = get_test_prices();
+        let candles = candles_from_closes(&prices);
+        let decimal_prices: Vec<Decimal> = prices.iter().map(|&p| Decimal::from_f64(p)).collect();
+        let decimal_candles = candles_from_closes(&decimal_prices);
+
+        let config = Config::wilder(14, 100);
+        let decimal_config = Config::<Decimal>::wilder(14, 100);
+        let expected = rsi_series(&candles, &config).unwrap();
+        let rsi_values = rsi_series(&decimal_candles, &decimal_config).unwrap();
+
+        assert_eq!(rsi_values.mask(), expected.mask());
+        for (i, (value, expected)) in rsi_values
+            .values()
+            .iter()
+            .zip(expected.values().iter())
+            .enumerate()
+        {
             assert!(
-                (rsi_value - expected).abs() < 0.01,
+                (value.to_f64().unwrap() - expected).abs() < 1e-9,
                 "RSI value mismatch at index {i}"
             );
         }
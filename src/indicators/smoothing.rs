@@ -0,0 +1,233 @@
+//! Smoothers shared by indicators that average an intermediate series.
+
+use crate::{
+    core::{Column, Ewm, EwmMode, Numeric, RollingMean, RollingWma},
+    indicators::{Config, EwmChain, Smoothing, dema, tema},
+};
+
+/// Incremental smoother selected by [`Smoothing`].
+///
+/// Every smoother produces a value from the first input on; values computed from
+/// fewer than [`Config::warmup`] inputs are partial averages that callers mask.
+/// Exponential smoothers weight their inputs according to [`Config::ewm_mode`],
+/// and DEMA and TEMA chain their stages like the moving averages of the same name.
+#[derive(Debug, Clone)]
+pub(crate) enum Smoother<T> {
+    Ema(Ewm<T>),
+    Sma(RollingMean<T>),
+    Wma(RollingWma<T>),
+    Dema(EwmChain<T, 2>),
+    Tema(EwmChain<T, 3>),
+}
+
+impl<T: Numeric> Smoother<T> {
+    /// Creates the smoother configured by `config`.
+    pub(crate) fn new(config: &Config<T>) -> Self {
+        let ewm = || Ewm::new(config.alpha, config.ewm_mode, 0);
+        match config.smoothing {
+            Smoothing::Ema | Smoothing::Wilder => Smoother::Ema(ewm()),
+            Smoothing::Sma => Smoother::Sma(RollingMean::new(config.period.max(1))),
+            Smoothing::Wma => Smoother::Wma(RollingWma::new(config.period.max(1))),
+            Smoothing::Dema => Smoother::Dema(EwmChain::new(ewm())),
+            Smoothing::Tema => Smoother::Tema(EwmChain::new(ewm())),
+        }
+    }
+
+    /// Folds the next input into the smoother and returns the smoothed value.
+    pub(crate) fn update(&mut self, value: T) -> T {
+        match self {
+            Smoother::Ema(ema) => {
+                ema.push(value);
+            }
+            Smoother::Sma(sma) => {
+                sma.push(value);
+            }
+            Smoother::Wma(wma) => {
+                wma.push(value);
+            }
+            Smoother::Dema(emas) => emas.push(value),
+            Smoother::Tema(emas) => emas.push(value),
+        }
+        self.value().unwrap()
+    }
+
+    /// Returns the smoothed value after applying `value` without modifying the smoother.
+    pub(crate) fn peek(&self, value: T) -> T {
+        match self {
+            Smoother::Ema(ema) => ema.next_mean(value),
+            _ => self.clone().update(value),
+        }
+    }
+
+    /// Returns the latest smoothed value, or None if no input has been processed.
+    pub(crate) fn value(&self) -> Option<T> {
+        match self {
+            Smoother::Ema(ema) => (ema.count() > 0).then(|| ema.mean()),
+            Smoother::Sma(sma) => sma.partial(),
+            Smoother::Wma(wma) => wma.partial(),
+            Smoother::Dema(emas) => emas.partial_values().map(dema),
+            Smoother::Tema(emas) => emas.partial_values().map(tema),
+        }
+    }
+}
+
+/// Smooths a whole column, producing one value per input.
+///
+/// The first `skip` inputs are placeholders: they are returned unchanged and not
+/// fed to the smoother.
+pub(crate) fn smooth<T: Numeric>(values: Column<T>, config: &Config<T>, skip: usize) -> Column<T> {
+    match config.smoothing {
+        // Reuse the column's in-place recursion, which performs the same arithmetic
+        Smoothing::Ema | Smoothing::Wilder
+            if skip == 0 && config.ewm_mode == EwmMode::Recursive =>
+        {
+            values.into_ewm_mean(config.alpha)
+        }
+        _ => {
+            let mut smoother = Smoother::new(config);
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    if i < skip {
+                        *value
+                    } else {
+                        smoother.update(*value)
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::MovingAverage;
+
+    const SMOOTHINGS: [Smoothing; 6] = [
+        Smoothing::Sma,
+        Smoothing::Ema,
+        Smoothing::Wilder,
+        Smoothing::Wma,
+        Smoothing::Dema,
+        Smoothing::Tema,
+    ];
+
+    fn get_test_values() -> Column<f64> {
+        (0..40)
+            .map(|i| 50.0 + ((i * 7) % 11) as f64 * 0.5)
+            .collect()
+    }
+
+    fn smooth_with(smoothing: Smoothing, values: &[f64]) -> Column<f64> {
+        let config = Config::ema(3, 100).with_smoothing(smoothing);
+        smooth(Column::from(values.to_vec()), &config, 0)
+    }
+
+    #[test]
+    fn test_smoothers() {
+        let values = [1.0, 2.0, 3.0, 4.0, 8.0];
+
+        let sma = smooth_with(Smoothing::Sma, &values);
+        assert_eq!(sma.as_slice(), &[1.0, 1.5, 2.0, 3.0, 5.0]);
+
+        let wma = smooth_with(Smoothing::Wma, &values);
+        assert_eq!(wma[2], (1.0 + 4.0 + 9.0) / 6.0);
+        assert_eq!(wma[4], (3.0 + 8.0 + 24.0) / 6.0);
+
+        // EMA with alpha = 0.5, and DEMA/TEMA composed from it
+        let ema = smooth_with(Smoothing::Ema, &values);
+        assert_eq!(ema.as_slice(), &[1.0, 1.5, 2.25, 3.125, 5.5625]);
+        let ema2 = ema.clone().into_ewm_mean(0.5);
+        let ema3 = ema2.clone().into_ewm_mean(0.5);
+        let dema = smooth_with(Smoothing::Dema, &values);
+        let tema = smooth_with(Smoothing::Tema, &values);
+        for i in 0..values.len() {
+            assert_eq!(dema[i], 2.0 * ema[i] - ema2[i]);
+            assert!((tema[i] - (3.0 * (ema[i] - ema2[i]) + ema3[i])).abs() < 1e-12);
+        }
+
+        assert_eq!(Smoothing::Tema.warmup(3), 7);
+        assert_eq!(Smoothing::Wilder.alpha::<f64>(4), Some(0.25));
+        assert_eq!(Smoothing::Sma.alpha::<f64>(4), None);
+    }
+
+    #[test]
+    fn test_sma_seeded_smoothers_match_moving_averages() {
+        let values = get_test_values();
+        for smoothing in SMOOTHINGS {
+            let config = Config::ema(5, 100)
+                .with_smoothing(smoothing)
+                .with_ewm_mode(EwmMode::SmaSeeded(5));
+            let smoothed = smooth(values.clone(), &config, 0);
+            let expected = values.moving_average(MovingAverage::from_config(&config));
+
+            assert_eq!(expected.first_valid(), Some(config.warmup() - 1));
+            for (i, expected) in expected.iter().enumerate() {
+                if let Some(expected) = expected {
+                    assert!(
+                        (smoothed[i] - expected).abs() < 1e-9,
+                        "{smoothing:?} at {i}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_window_recovers_from_non_finite_inputs() {
+        let values = [1.0, f64::NAN, 2.0, 3.0, 4.0, 5.0];
+        let sma = smooth_with(Smoothing::Sma, &values);
+        let wma = smooth_with(Smoothing::Wma, &values);
+        for i in 1..4 {
+            assert!(sma[i].is_nan() && wma[i].is_nan(), "{i}");
+        }
+        assert_eq!(&sma.as_slice()[4..], &[3.0, 4.0]);
+        assert_eq!(wma[5], (3.0 + 8.0 + 15.0) / 6.0);
+    }
+
+    #[test]
+    fn test_ewm_modes() {
+        let values = get_test_values();
+        let config = Config::ema(3, 100).with_ewm_mode(EwmMode::Adjusted);
+        let adjusted = values.ewm_mean(0.5, EwmMode::Adjusted, 0);
+        assert_eq!(&smooth(values.clone(), &config, 0), adjusted.values());
+
+        // The default recursive mode is seeded with the first input
+        let recursive = smooth(values.clone(), &Config::ema(3, 100), 0);
+        assert_eq!(recursive[0], values[0]);
+        assert_ne!(recursive[5], adjusted.values()[5]);
+    }
+
+    #[test]
+    fn test_streaming_matches_batch() {
+        let values = get_test_values();
+        for smoothing in SMOOTHINGS {
+            for mode in [EwmMode::Recursive, EwmMode::Adjusted, EwmMode::SmaSeeded(4)] {
+                let config = Config::ema(4, 100)
+                    .with_smoothing(smoothing)
+                    .with_ewm_mode(mode);
+                let expected = smooth(values.clone(), &config, 0);
+
+                let mut smoother = Smoother::new(&config);
+                assert_eq!(smoother.value(), None);
+                for (i, value) in values.iter().enumerate() {
+                    assert_eq!(smoother.peek(*value), expected[i], "{smoothing:?} {mode:?}");
+                    assert_eq!(
+                        smoother.update(*value),
+                        expected[i],
+                        "{smoothing:?} {mode:?}"
+                    );
+                    assert_eq!(smoother.value(), Some(expected[i]));
+                }
+
+                // Skipped placeholders are passed through and not smoothed
+                let skipped = smooth(values.clone(), &config, 1);
+                let tail = smooth(Column::from(values.as_slice()[1..].to_vec()), &config, 0);
+                assert_eq!(skipped[0], values[0]);
+                assert_eq!(&skipped.as_slice()[1..], tail.as_slice());
+            }
+        }
+    }
+}
@@ -2,6 +2,12 @@
 //!
 //! Fast, extensible technical analysis library for Rust with trait-based numeric types.
 //!
+//! ## Features
+//! - `decimal`: implements [`core::Numeric`] for `rust_decimal::Decimal` for exact
+//!   decimal arithmetic.
+//! - `chrono-tz`: daily, weekly and monthly [`core::Timeframe`]s bound to an IANA
+//!   timezone, whose boundaries follow daylight saving time.
+//!
 //! ## Example
 //! ```rust
 //! use mizuhiki_ta::{
@@ -26,13 +32,18 @@
 //! }
 //!
 //! // Calculate RSI with 14-period configuration
-//! let config = Config::new_f64(14, 50);
-//! let rsi_values = rsi_series(&candles, config)?;
+//! let config = Config::ema(14, 50);
+//! let rsi_values = rsi_series(&candles, &config)?;
 //!
 //! println!("RSI calculated for {} candles", rsi_values.len());
 //! # Ok(())
 //! # }
 //! ```
 
+pub mod bars;
 pub mod core;
 pub mod indicators;
+pub mod universe;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,49 @@
+//! Fixtures and assertions shared by the unit tests.
+
+use crate::core::{Candle, CandleSeries, MaskedColumn, Numeric};
+
+/// Closes of a 39-candle series used across the indicator tests.
+pub(crate) const CLOSES: [f64; 39] = [
+    48.16, 48.61, 48.75, 48.63, 48.74, 49.03, 49.07, 49.32, 49.91, 50.13, 49.53, 49.50, 49.75,
+    50.03, 49.61, 49.80, 50.20, 50.73, 50.94, 51.08, 50.97, 50.55, 50.42, 50.14, 50.23, 50.41,
+    50.53, 50.60, 50.81, 50.95, 51.01, 51.07, 50.65, 50.16, 49.85, 49.77, 49.66, 49.49, 49.21,
+];
+
+/// Builds one-minute candles from `closes`, with highs and lows 0.25 away from the close.
+pub(crate) fn candles_from_closes<T: Numeric>(closes: &[T]) -> CandleSeries<T> {
+    candles_from_closes_at(closes, 0)
+}
+
+/// Builds candles like [`candles_from_closes`], where the first candle is the one
+/// at index `first` of a series starting at zero.
+pub(crate) fn candles_from_closes_at<T: Numeric>(closes: &[T], first: usize) -> CandleSeries<T> {
+    let spread = T::from_f64(0.25);
+    let mut candles = CandleSeries::new(60);
+    for (i, &close) in closes.iter().enumerate() {
+        candles.push_candle_unchecked(
+            Candle {
+                open: close,
+                high: close + spread,
+                low: close - spread,
+                close,
+                volume: T::ONE,
+            },
+            ((first + i) as u64) * 60,
+        );
+    }
+    candles
+}
+
+/// Asserts that a masked column matches `expected` within 1e-9, mask included.
+pub(crate) fn assert_close(actual: &MaskedColumn<f64>, expected: &[Option<f64>]) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (value, expected)) in actual.iter().zip(expected.iter()).enumerate() {
+        match (value, expected) {
+            (Some(value), Some(expected)) => {
+                assert!((value - expected).abs() < 1e-9, "Mismatch at index {i}")
+            }
+            (None, None) => {}
+            _ => panic!("Mask mismatch at index {i}"),
+        }
+    }
+}
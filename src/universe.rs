@@ -0,0 +1,395 @@
+//! Multi-symbol container routing ticks to per-symbol candle series.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+    core::{Aligned, CandleSeries, Error, MaskedColumn, Numeric, Timeframe, valid_timeframe},
+    indicators::Indicator,
+};
+
+/// Collection of candle series keyed by symbol.
+///
+/// Every series created by the universe shares the same timeframe and, if set,
+/// the same maximum length. Symbols are kept in sorted order.
+///
+/// # Example
+/// ```rust
+/// use mizuhiki_ta::{
+///     indicators::{Config, RsiState},
+///     universe::Universe,
+/// };
+///
+/// # fn main() -> Result<(), mizuhiki_ta::core::Error> {
+/// let mut universe = Universe::<f64>::new(60_000);
+/// for i in 0..20u64 {
+///     universe.push("BTC", 100.0 + i as f64, 1.0, i * 60_000)?;
+///     universe.push("ETH", 50.0 - i as f64, 1.0, i * 60_000)?;
+/// }
+///
+/// let config = Config::wilder(14, 100);
+/// let table = universe.cross_section(&[("rsi", &RsiState::new(&config))]);
+/// assert_eq!(table.rank("rsi", true)[0].0, "BTC");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Universe<T> {
+    timeframe: Timeframe,
+    max_len: Option<usize>,
+    series: BTreeMap<String, CandleSeries<T>>,
+}
+
+impl<T: Numeric> Universe<T> {
+    /// Creates an empty universe whose series use the specified timeframe.
+    ///
+    /// # Panics
+    /// Panics if the timeframe is empty.
+    pub fn new(timeframe: impl Into<Timeframe>) -> Self {
+        Self {
+            timeframe: valid_timeframe(timeframe.into()),
+            max_len: None,
+            series: BTreeMap::new(),
+        }
+    }
+
+    /// Creates an empty universe whose series retain at most `max_len` candles each.
+    ///
+    /// # Panics
+    /// Panics if `max_len` is zero or the timeframe is empty.
+    pub fn with_max_len(timeframe: impl Into<Timeframe>, max_len: usize) -> Self {
+        if max_len == 0 {
+            panic!("max_len must be greater than zero");
+        }
+
+        Self {
+            timeframe: valid_timeframe(timeframe.into()),
+            max_len: Some(max_len),
+            series: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the timeframe of series created by the universe.
+    pub fn timeframe(&self) -> Timeframe {
+        self.timeframe
+    }
+
+    /// Returns the number of symbols.
+    pub fn len(&self) -> usize {
+        self.series.len()
+    }
+
+    /// Returns true if the universe contains no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    /// Returns an iterator over the symbols in sorted order.
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.series.keys().map(String::as_str)
+    }
+
+    /// Returns an iterator over symbols and their series in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &CandleSeries<T>)> {
+        self.series
+            .iter()
+            .map(|(symbol, series)| (symbol.as_str(), series))
+    }
+
+    /// Gets the series for a symbol.
+    pub fn get(&self, symbol: &str) -> Option<&CandleSeries<T>> {
+        self.series.get(symbol)
+    }
+
+    /// Gets the series for a symbol mutably, e.g. to change its gap policy.
+    pub fn get_mut(&mut self, symbol: &str) -> Option<&mut CandleSeries<T>> {
+        self.series.get_mut(symbol)
+    }
+
+    /// Inserts an existing series, returning the one it replaces.
+    pub fn insert(
+        &mut self,
+        symbol: impl Into<String>,
+        series: CandleSeries<T>,
+    ) -> Option<CandleSeries<T>> {
+        self.series.insert(symbol.into(), series)
+    }
+
+    /// Removes a symbol and returns its series.
+    pub fn remove(&mut self, symbol: &str) -> Option<CandleSeries<T>> {
+        self.series.remove(symbol)
+    }
+
+    /// Pushes a price tick to the series of `symbol`, creating it if needed.
+    ///
+    /// # Errors
+    /// Returns the error of [`CandleSeries::push`].
+    pub fn push(&mut self, symbol: &str, price: T, vol: T, ts: u64) -> Result<(), Error> {
+        self.series_mut(symbol).push(price, vol, ts)
+    }
+
+    /// Pushes a price tick to the series of `symbol` without timestamp validation,
+    /// creating the series if needed.
+    pub fn push_unchecked(&mut self, symbol: &str, price: T, vol: T, ts: u64) {
+        self.series_mut(symbol).push_unchecked(price, vol, ts)
+    }
+
+    /// Computes an indicator series for every symbol.
+    pub fn compute<I>(
+        &self,
+        indicator: &I,
+    ) -> BTreeMap<&str, Result<Aligned<MaskedColumn<T>>, Error>>
+    where
+        I: Indicator<T> + ?Sized,
+    {
+        self.iter()
+            .map(|(symbol, series)| (symbol, indicator.compute_series(series.view())))
+            .collect()
+    }
+
+    /// Computes the latest value of each named indicator for every symbol.
+    ///
+    /// Only the latest value is computed for each symbol, see [`Indicator::latest`].
+    /// Values are missing where a symbol does not have enough data or the latest
+    /// value is still within the warmup.
+    pub fn cross_section(&self, indicators: &[(&str, &dyn Indicator<T>)]) -> CrossSection<T> {
+        let columns = indicators
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        let rows = self
+            .iter()
+            .map(|(symbol, series)| {
+                let values = indicators
+                    .iter()
+                    .map(|(_, indicator)| indicator.latest(series.view()).ok())
+                    .collect();
+                (symbol.to_string(), values)
+            })
+            .collect();
+
+        CrossSection { columns, rows }
+    }
+
+    fn series_mut(&mut self, symbol: &str) -> &mut CandleSeries<T> {
+        if !self.series.contains_key(symbol) {
+            let series = match self.max_len {
+                Some(max_len) => CandleSeries::with_max_len(self.timeframe, max_len),
+                None => CandleSeries::new(self.timeframe),
+            };
+            self.series.insert(symbol.to_string(), series);
+        }
+        self.series.get_mut(symbol).unwrap()
+    }
+}
+
+/// Table of the latest indicator values, one row per symbol and one column per indicator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossSection<T> {
+    columns: Vec<String>,
+    rows: BTreeMap<String, Vec<Option<T>>>,
+}
+
+impl<T: Numeric> CrossSection<T> {
+    /// Returns the indicator names.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the number of symbols.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns true if the table contains no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns an iterator over symbols and their values in sorted order.
+    pub fn rows(&self) -> impl Iterator<Item = (&str, &[Option<T>])> {
+        self.rows
+            .iter()
+            .map(|(symbol, values)| (symbol.as_str(), values.as_slice()))
+    }
+
+    /// Gets the value of an indicator for a symbol.
+    pub fn get(&self, symbol: &str, column: &str) -> Option<T> {
+        let index = self.column_index(column)?;
+        self.rows.get(symbol)?[index]
+    }
+
+    /// Returns the symbols whose row matches the predicate.
+    pub fn filter<F>(&self, mut predicate: F) -> Vec<&str>
+    where
+        F: FnMut(&[Option<T>]) -> bool,
+    {
+        self.rows()
+            .filter(|(_, values)| predicate(values))
+            .map(|(symbol, _)| symbol)
+            .collect()
+    }
+
+    /// Returns the symbols with a value for `column`, sorted by that value.
+    ///
+    /// Values that cannot be ordered, such as NaN, are left out.
+    pub fn rank(&self, column: &str, descending: bool) -> Vec<(&str, T)> {
+        let Some(index) = self.column_index(column) else {
+            return Vec::new();
+        };
+
+        let mut ranked: Vec<(&str, T)> = self
+            .rows()
+            .filter_map(|(symbol, values)| values[index].map(|value| (symbol, value)))
+            .filter(|(_, value)| value.partial_cmp(value).is_some())
+            .collect();
+        // Every remaining value is comparable, so this is a total order
+        ranked.sort_by(|a, b| {
+            let ordering = a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        ranked
+    }
+
+    fn column_index(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|name| name == column)
+    }
+}
+
+impl<T: Numeric + Display> Display for CrossSection<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<12}", "SYMBOL")?;
+        for column in &self.columns {
+            write!(f, " {:>12}", column)?;
+        }
+
+        for (symbol, values) in &self.rows {
+            write!(f, "\n{:<12}", symbol)?;
+            for value in values {
+                match value {
+                    Some(value) => write!(f, " {:>12.2}", value)?,
+                    None => write!(f, " {:>12}", "-")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Config, NatrState, RsiState, natr_latest, rsi_latest, rsi_series};
+
+    fn get_test_universe() -> Universe<f64> {
+        let mut universe = Universe::with_max_len(60, 50);
+        for i in 0..30u64 {
+            let wave = (i % 4) as f64;
+            universe
+                .push("AAA", 100.0 + i as f64 + wave, 1.0, i * 60)
+                .unwrap();
+            universe
+                .push("BBB", 100.0 - i as f64 + wave, 1.0, i * 60)
+                .unwrap();
+        }
+        // Not enough data for a 14-period indicator
+        universe.push("CCC", 10.0, 1.0, 0).unwrap();
+        universe
+    }
+
+    #[test]
+    fn test_routing() {
+        let mut universe = get_test_universe();
+        assert_eq!(
+            universe.symbols().collect::<Vec<_>>(),
+            vec!["AAA", "BBB", "CCC"]
+        );
+        assert_eq!(universe.get("AAA").unwrap().len(), 30);
+        assert_eq!(universe.get("AAA").unwrap().max_len(), Some(50));
+        assert!(universe.push("AAA", 1.0, 1.0, 0).is_err());
+
+        universe.push_unchecked("DDD", 1.0, 1.0, 0);
+        assert_eq!(universe.len(), 4);
+        assert!(universe.remove("DDD").is_some());
+    }
+
+    #[test]
+    fn test_compute() {
+        let universe = get_test_universe();
+        let config = Config::wilder(14, 100);
+        let results = universe.compute(&RsiState::new(&config));
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results["AAA"].as_ref().unwrap(),
+            &rsi_series(universe.get("AAA").unwrap(), &config).unwrap()
+        );
+        assert!(matches!(results["CCC"], Err(Error::NotEnoughData)));
+    }
+
+    #[test]
+    fn test_cross_section() {
+        let universe = get_test_universe();
+        let config = Config::wilder(14, 100);
+        let rsi = RsiState::new(&config);
+        let natr = NatrState::new(&config);
+        let table = universe.cross_section(&[("rsi", &rsi), ("natr", &natr)]);
+
+        assert_eq!(table.columns(), &["rsi".to_string(), "natr".to_string()]);
+        assert_eq!(table.len(), 3);
+        let aaa = universe.get("AAA").unwrap();
+        assert_eq!(table.get("AAA", "rsi"), rsi_latest(aaa, &config).ok());
+        assert_eq!(table.get("AAA", "natr"), natr_latest(aaa, &config).ok());
+        assert!(table.get("AAA", "rsi").unwrap() > 50.0);
+        assert!(table.get("BBB", "rsi").unwrap() < 50.0);
+        assert_eq!(table.get("CCC", "rsi"), None);
+        assert_eq!(table.get("AAA", "missing"), None);
+
+        let ranked = table.rank("rsi", false);
+        assert_eq!(
+            ranked.iter().map(|(s, _)| *s).collect::<Vec<_>>(),
+            vec!["BBB", "AAA"]
+        );
+
+        let oversold = table.filter(|values| values[0].is_some_and(|rsi| rsi < 50.0));
+        assert_eq!(oversold, vec!["BBB"]);
+        assert!(table.to_string().contains("CCC"));
+    }
+
+    #[test]
+    #[should_panic(expected = "timeframe length must be greater than zero")]
+    fn test_empty_timeframe_panics() {
+        let _ = Universe::<f64>::new(Timeframe::Fixed {
+            length: 0,
+            offset: 0,
+        });
+    }
+
+    #[test]
+    fn test_rank_skips_nan() {
+        let mut rows: BTreeMap<String, Vec<Option<f64>>> = (0..100)
+            .map(|i| (format!("S{i:03}"), vec![Some(((i * 37) % 100) as f64)]))
+            .collect();
+        rows.insert("NAN".to_string(), vec![Some(f64::NAN)]);
+        rows.insert("NONE".to_string(), vec![None]);
+        let table = CrossSection {
+            columns: vec!["value".to_string()],
+            rows,
+        };
+
+        for descending in [false, true] {
+            let ranked = table.rank("value", descending);
+            assert_eq!(ranked.len(), 100);
+            assert!(ranked.iter().all(|(symbol, _)| *symbol != "NAN"));
+            assert!(ranked.windows(2).all(|pair| if descending {
+                pair[0].1 >= pair[1].1
+            } else {
+                pair[0].1 <= pair[1].1
+            }));
+        }
+    }
+}